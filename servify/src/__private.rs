@@ -0,0 +1,174 @@
+//! Items used by the code generated by `servify_macro`. Not part of the public API.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::future::{poll_fn, ready, Future, Ready};
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::sync::oneshot::{self, error::RecvError};
+
+use crate::{
+    Call, Completed, Context, Error, EventStream, ResponseStream, Scheduled, StreamSender,
+};
+
+pub use crate::shutdown::ShutdownSignal;
+pub use crate::trace::CallSpan;
+#[cfg(feature = "tracing")]
+pub use tracing;
+
+/// How the server answers a request: through a oneshot channel, or not at all for
+/// `#[servify(oneway)]` methods.
+pub trait Responder: Sized {
+    type Response;
+    type Receiver: Future<Output = Result<Result<Self::Response, Error>, RecvError>>;
+
+    fn channel() -> (Self, Self::Receiver);
+
+    /// Returns whether the client stopped waiting for the response.
+    fn is_closed(&self) -> bool;
+
+    fn send(self, res: Result<Self::Response, Error>) -> Result<(), Result<Self::Response, Error>>;
+}
+
+impl<T> Responder for oneshot::Sender<Result<T, Error>> {
+    type Response = T;
+    type Receiver = oneshot::Receiver<Result<T, Error>>;
+
+    fn channel() -> (Self, Self::Receiver) {
+        oneshot::channel()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.is_closed()
+    }
+
+    fn send(self, res: Result<T, Error>) -> Result<(), Result<T, Error>> {
+        self.send(res)
+    }
+}
+
+/// Oneway requests are answered as soon as they are enqueued.
+impl Responder for () {
+    type Response = ();
+    type Receiver = Ready<Result<Result<(), Error>, RecvError>>;
+
+    fn channel() -> (Self, Self::Receiver) {
+        ((), ready(Ok(Ok(()))))
+    }
+
+    fn is_closed(&self) -> bool {
+        false
+    }
+
+    fn send(self, _: Result<(), Error>) -> Result<(), Result<(), Error>> {
+        Ok(())
+    }
+}
+
+/// Creates the channel behind a `#[servify(stream)]` call.
+pub fn stream_channel<T>() -> (StreamSender<T>, ResponseStream<T>) {
+    StreamSender::channel()
+}
+
+/// Ties the pending reply of a `#[servify(stream)]` call to the lifetime of its stream, which
+/// reports it through `ResponseStream::finish`.
+pub fn keep_alive<T, R>(stream: ResponseStream<T>, reply: R) -> ResponseStream<T>
+where
+    R: Future<Output = Result<Result<(), Error>, RecvError>> + Send + 'static,
+{
+    stream.keep_alive(async move { reply.await.map_err(|_| Error::ServerDropped)? })
+}
+
+/// Creates the channel behind the events of a service, emitted through the `Context` held by the
+/// `Receiver` and subscribed to through the `WeakSender` held by the `Client`.
+pub fn event_channel<E: Clone>() -> (Context<E>, broadcast::WeakSender<E>) {
+    Context::channel()
+}
+
+/// The `Context` handed to the methods of a service declared without `events`.
+pub fn no_events() -> Context<()> {
+    Context::channel().0
+}
+
+/// Subscribes to the events of a service on behalf of its `Client`.
+pub fn subscribe<E: Clone + Send + 'static>(events: &broadcast::WeakSender<E>) -> EventStream<E> {
+    match events.upgrade() {
+        Some(events) => EventStream::new(events.subscribe()),
+        // The server has stopped, so the stream ends right away.
+        None => EventStream::new(broadcast::channel(1).1),
+    }
+}
+
+/// Runs `call` once `deadline` is reached, on behalf of a generated `Scheduler`.
+pub fn schedule<T: Send + 'static>(
+    deadline: tokio::time::Instant,
+    call: impl Future<Output = Result<T, Error>> + Send + 'static,
+) -> Scheduled<T> {
+    Scheduled::new(deadline, call)
+}
+
+/// Creates the [`Call`] handed to `Middleware::before`.
+pub fn call<'a>(method: &'static str, request: Option<&'a dyn Debug>) -> Call<'a> {
+    Call::new(method, request)
+}
+
+/// Creates the [`Completed`] handed to `Middleware::after`.
+pub fn completed<'a>(
+    method: &'static str,
+    elapsed: Duration,
+    response: Result<Option<&'a dyn Debug>, &'a Error>,
+) -> Completed<'a> {
+    Completed::new(method, elapsed, response)
+}
+
+/// Borrows a value as `dyn Debug` when its type implements `Debug`. Bring [`DebugProbe`] and
+/// [`FallbackProbe`] into scope and call `(&Probe(&value)).debug()`; method resolution prefers
+/// `DebugProbe` and falls back to `None` for other types.
+pub struct Probe<'a, T>(pub &'a T);
+
+pub trait DebugProbe<'a> {
+    fn debug(&self) -> Option<&'a dyn Debug>;
+}
+
+impl<'a, T: Debug> DebugProbe<'a> for Probe<'a, T> {
+    fn debug(&self) -> Option<&'a dyn Debug> {
+        Some(self.0)
+    }
+}
+
+pub trait FallbackProbe<'a> {
+    fn debug(&self) -> Option<&'a dyn Debug>;
+}
+
+impl<'a, T> FallbackProbe<'a> for &Probe<'a, T> {
+    fn debug(&self) -> Option<&'a dyn Debug> {
+        None
+    }
+}
+
+/// Drives `future` to completion, turning a panic raised while polling it into [`Error::Panicked`].
+pub async fn catch_unwind<F: Future>(future: F) -> Result<F::Output, Error> {
+    let mut future = pin!(future);
+    poll_fn(
+        |cx| match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(Error::Panicked(panic_message(payload)))),
+        },
+    )
+    .await
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Box<dyn Any>".to_string(),
+        },
+    }
+}
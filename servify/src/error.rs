@@ -0,0 +1,37 @@
+use std::fmt;
+
+/// An error returned by the fallible `try_*` methods of a generated `Client`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The server is no longer receiving messages, so the request could not be delivered.
+    Disconnected,
+    /// The server accepted the request but dropped it without replying.
+    ServerDropped,
+    /// The server did not reply within the timeout configured on the client.
+    Timeout,
+    /// The handler panicked while processing the request. Holds the panic message.
+    Panicked(String),
+    /// The server was shut down before handling the request.
+    ShuttingDown,
+    /// The scheduled call was cancelled before it completed.
+    Cancelled,
+    /// A middleware rejected the request before it was handled. Holds the reason.
+    Rejected(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Disconnected => f.write_str("the server is no longer receiving messages"),
+            Error::ServerDropped => f.write_str("the server dropped the request without replying"),
+            Error::Timeout => f.write_str("the server did not reply in time"),
+            Error::Panicked(message) => write!(f, "the handler panicked: {message}"),
+            Error::ShuttingDown => f.write_str("the server is shutting down"),
+            Error::Cancelled => f.write_str("the scheduled call was cancelled"),
+            Error::Rejected(reason) => write!(f, "the request was rejected: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::pin::Pin;
+use std::task::{ready, Context as TaskContext, Poll};
+
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::Stream;
+
+/// How many events a subscriber can fall behind before it misses the oldest ones.
+const CAPACITY: usize = 64;
+
+/// Emits the events of a service declared with `#[servify::service(events = E)]`.
+///
+/// Exported methods receive it by taking it as an argument, which the client leaves out:
+///
+/// ```ignore
+/// fn increment(&mut self, count: u32, ctx: servify::Context<CounterChanged>) {
+///     self.count += count;
+///     ctx.emit(CounterChanged(self.count));
+/// }
+/// ```
+pub struct Context<E> {
+    events: broadcast::Sender<E>,
+}
+
+impl<E: Clone> Context<E> {
+    pub(crate) fn channel() -> (Self, broadcast::WeakSender<E>) {
+        let (events, _) = broadcast::channel(CAPACITY);
+        let weak = events.downgrade();
+        (Self { events }, weak)
+    }
+
+    /// Sends `event` to every current subscriber, returning how many there are.
+    pub fn emit(&self, event: E) -> usize {
+        self.events.send(event).unwrap_or(0)
+    }
+
+    /// Returns how many subscribers the events are sent to.
+    pub fn subscribers(&self) -> usize {
+        self.events.receiver_count()
+    }
+}
+
+impl<E> Clone for Context<E> {
+    fn clone(&self) -> Self {
+        Self {
+            events: self.events.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for Context<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context").finish_non_exhaustive()
+    }
+}
+
+/// The events emitted by a service after `Client::subscribe` was called, in order.
+///
+/// A subscriber that falls more than 64 events behind skips the oldest ones. The stream ends
+/// once the server stops.
+pub struct EventStream<E> {
+    events: BroadcastStream<E>,
+}
+
+impl<E: Clone + Send + 'static> EventStream<E> {
+    pub(crate) fn new(events: broadcast::Receiver<E>) -> Self {
+        Self {
+            events: BroadcastStream::new(events),
+        }
+    }
+}
+
+impl<E: Clone + Send + 'static> Stream for EventStream<E> {
+    type Item = E;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<E>> {
+        loop {
+            match ready!(Pin::new(&mut self.events).poll_next(cx)) {
+                Some(Ok(event)) => return Poll::Ready(Some(event)),
+                // Lagged behind, continue from the oldest event still buffered.
+                Some(Err(_)) => continue,
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<E> fmt::Debug for EventStream<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream").finish_non_exhaustive()
+    }
+}
@@ -1,6 +1,27 @@
+mod error;
+mod events;
+mod middleware;
+mod schedule;
+mod shutdown;
+mod spawn;
+mod stream;
+mod trace;
+
+#[doc(hidden)]
+pub mod __private;
+
+pub use error::Error;
+pub use events::{Context, EventStream};
+pub use middleware::{Call, Completed, Middleware};
+pub use schedule::Scheduled;
 pub use servify_macro::{export, service};
+pub use shutdown::{ShutdownHandle, ShutdownMode};
+pub use spawn::SpawnConfig;
+pub use stream::{ResponseStream, StreamSender};
 
 pub trait ServifyExport {
     type Request;
     type Response;
+    /// What the server answers the request through.
+    type Responder;
 }
@@ -0,0 +1,95 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::Error;
+
+/// Runs around every request a server handles, for logging, auditing, authorization or metrics
+/// in one place.
+///
+/// A middleware holding state, such as a metrics registry, is handed to the server through
+/// [`SpawnConfig::middleware`](crate::SpawnConfig::middleware) or `Receiver::with_middleware`.
+/// A service can also declare one with `#[servify::service(middleware = Type)]`, created through
+/// [`Default`] when the server starts listening without one.
+///
+/// ```ignore
+/// #[derive(Default)]
+/// struct Log;
+///
+/// impl servify::Middleware for Log {
+///     fn after(&self, completed: &servify::Completed<'_>) {
+///         println!("{} took {:?}", completed.method(), completed.elapsed());
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    /// Runs before the handler. Returning an error skips the handler and replies with the error.
+    fn before(&self, call: &Call<'_>) -> Result<(), Error> {
+        let _ = call;
+        Ok(())
+    }
+
+    /// Runs after the handler, before the reply is sent.
+    fn after(&self, completed: &Completed<'_>) {
+        let _ = completed;
+    }
+}
+
+/// A request about to be handled, seen by [`Middleware::before`].
+#[derive(Debug)]
+pub struct Call<'a> {
+    method: &'static str,
+    request: Option<&'a dyn Debug>,
+}
+
+impl<'a> Call<'a> {
+    pub(crate) fn new(method: &'static str, request: Option<&'a dyn Debug>) -> Self {
+        Self { method, request }
+    }
+
+    /// The name the method is exported under.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The request, if its type implements [`Debug`].
+    pub fn request(&self) -> Option<&'a dyn Debug> {
+        self.request
+    }
+}
+
+/// A handled request, seen by [`Middleware::after`].
+#[derive(Debug)]
+pub struct Completed<'a> {
+    method: &'static str,
+    elapsed: Duration,
+    response: Result<Option<&'a dyn Debug>, &'a Error>,
+}
+
+impl<'a> Completed<'a> {
+    pub(crate) fn new(
+        method: &'static str,
+        elapsed: Duration,
+        response: Result<Option<&'a dyn Debug>, &'a Error>,
+    ) -> Self {
+        Self {
+            method,
+            elapsed,
+            response,
+        }
+    }
+
+    /// The name the method is exported under.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// How long the handler took.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The response, if its type implements [`Debug`], or the error the handler failed with.
+    pub fn response(&self) -> Result<Option<&'a dyn Debug>, &'a Error> {
+        self.response
+    }
+}
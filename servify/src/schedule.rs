@@ -0,0 +1,73 @@
+use std::fmt;
+use std::future::Future;
+use std::panic;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant};
+
+use crate::Error;
+
+/// A call scheduled through `Client::after` or `Client::at`, resolving to its result.
+///
+/// Dropping the handle leaves the call scheduled. Cancelling it after the message was delivered
+/// only stops waiting for the reply.
+///
+/// ```ignore
+/// let reset = client.after(Duration::from_secs(10)).reset();
+/// if activity_seen {
+///     reset.cancel();
+/// }
+/// ```
+pub struct Scheduled<T> {
+    task: JoinHandle<Result<T, Error>>,
+}
+
+impl<T: Send + 'static> Scheduled<T> {
+    pub(crate) fn new(
+        deadline: Instant,
+        call: impl Future<Output = Result<T, Error>> + Send + 'static,
+    ) -> Self {
+        // The call opens its span inside the task, as a child of the span it was scheduled from.
+        #[cfg(feature = "tracing")]
+        let call = tracing::Instrument::in_current_span(call);
+        let task = tokio::spawn(async move {
+            time::sleep_until(deadline).await;
+            call.await
+        });
+        Self { task }
+    }
+}
+
+impl<T> Scheduled<T> {
+    /// Cancels the call. Awaiting the handle afterwards fails with [`Error::Cancelled`].
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+
+    /// Returns whether the call has completed or was cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl<T> Future for Scheduled<T> {
+    type Output = Result<T, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match ready!(Pin::new(&mut self.task).poll(cx)) {
+            Ok(res) => Poll::Ready(res),
+            Err(err) if err.is_cancelled() => Poll::Ready(Err(Error::Cancelled)),
+            Err(err) => panic::resume_unwind(err.into_panic()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Scheduled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduled")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// How a server treats the messages already queued when it is asked to shut down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Handle every message queued before the request, then stop.
+    Drain,
+    /// Reply [`Error::ShuttingDown`](crate::Error::ShuttingDown) to every queued message, then stop.
+    Reject,
+}
+
+/// A handle for asking a running server to stop.
+///
+/// Once a shutdown has been requested, the server stops accepting new messages and
+/// `Server::listen` returns the final server state after the queued messages are dealt with.
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    tx: Arc<watch::Sender<Option<ShutdownMode>>>,
+}
+
+impl ShutdownHandle {
+    /// Requests a shutdown. Only the first request takes effect.
+    pub fn shutdown(&self, mode: ShutdownMode) {
+        self.tx.send_if_modified(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(mode);
+            true
+        });
+    }
+
+    /// Requests a shutdown that handles the queued messages first.
+    pub fn drain(&self) {
+        self.shutdown(ShutdownMode::Drain);
+    }
+
+    /// Requests a shutdown that rejects the queued messages.
+    pub fn reject(&self) {
+        self.shutdown(ShutdownMode::Reject);
+    }
+}
+
+/// The server side of a [`ShutdownHandle`], owned by a generated `Receiver`. Clones wait for the
+/// same handle.
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal {
+    handle: ShutdownHandle,
+    rx: watch::Receiver<Option<ShutdownMode>>,
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        let (tx, rx) = watch::channel(None);
+        Self {
+            handle: ShutdownHandle { tx: Arc::new(tx) },
+            rx,
+        }
+    }
+}
+
+impl ShutdownSignal {
+    pub fn handle(&self) -> ShutdownHandle {
+        self.handle.clone()
+    }
+
+    /// Waits until a shutdown is requested. Cancel safe.
+    pub async fn requested(&mut self) -> ShutdownMode {
+        loop {
+            if let Some(mode) = *self.rx.borrow_and_update() {
+                return mode;
+            }
+            // `self.handle` keeps the sender alive, so the channel never closes.
+            let _ = self.rx.changed().await;
+        }
+    }
+}
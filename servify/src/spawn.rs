@@ -0,0 +1,128 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+
+use crate::__private::ShutdownSignal;
+use crate::{Middleware, ShutdownHandle};
+
+/// Options for starting a server with a generated `spawn` function.
+///
+/// ```ignore
+/// let (client, server) = Counter::spawn(
+///     Counter::Server { count: 0 },
+///     servify::SpawnConfig::new().buffer(64).name("counter"),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct SpawnConfig {
+    buffer: usize,
+    name: Option<String>,
+    runtime: Option<Handle>,
+    middleware: Option<Arc<dyn Middleware>>,
+    shutdown: ShutdownSignal,
+}
+
+impl Default for SpawnConfig {
+    fn default() -> Self {
+        Self {
+            buffer: 32,
+            name: None,
+            runtime: None,
+            middleware: None,
+            shutdown: ShutdownSignal::default(),
+        }
+    }
+}
+
+impl SpawnConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many messages can be queued before callers wait. Defaults to 32.
+    pub fn buffer(mut self, buffer: usize) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    /// Names the server task. The name is only visible to tools such as `tokio-console`,
+    /// which requires building with `--cfg tokio_unstable`.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Spawns the server onto `runtime` instead of the runtime `spawn` is called from.
+    pub fn runtime(mut self, runtime: Handle) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Runs `middleware` around every request, in place of the one the service declares with
+    /// `#[servify::service(middleware = Type)]`.
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middleware = Some(Arc::new(middleware));
+        self
+    }
+
+    /// Returns a handle that asks the server spawned with this config to stop. Servers spawned
+    /// with clones of the config share the handle.
+    ///
+    /// ```ignore
+    /// let config = servify::SpawnConfig::new();
+    /// let shutdown = config.shutdown_handle();
+    /// let (client, server) = Counter::spawn(Counter::Server { count: 0 }, config);
+    /// shutdown.drain();
+    /// ```
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.handle()
+    }
+
+    /// Returns the configured buffer size.
+    pub fn buffer_size(&self) -> usize {
+        self.buffer
+    }
+
+    #[doc(hidden)]
+    pub fn middleware_instance(&self) -> Option<Arc<dyn Middleware>> {
+        self.middleware.clone()
+    }
+
+    #[doc(hidden)]
+    pub fn shutdown_signal(&self) -> ShutdownSignal {
+        self.shutdown.clone()
+    }
+
+    #[doc(hidden)]
+    pub fn spawn<F>(self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let runtime = self.runtime.unwrap_or_else(Handle::current);
+
+        #[cfg(tokio_unstable)]
+        if let Some(name) = &self.name {
+            return tokio::task::Builder::new()
+                .name(name)
+                .spawn_on(future, &runtime)
+                .expect("failed to spawn the server task");
+        }
+
+        runtime.spawn(future)
+    }
+}
+
+impl fmt::Debug for SpawnConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnConfig")
+            .field("buffer", &self.buffer)
+            .field("name", &self.name)
+            .field("runtime", &self.runtime)
+            .field("middleware", &self.middleware.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::Error;
+
+/// The handler side of a `#[servify(stream)]` method, feeding items to the client's
+/// [`ResponseStream`].
+///
+/// The handler runs on the server loop, so items are buffered without limit instead of making it
+/// wait for a client that is not reading yet. Handlers producing unbounded output should check
+/// [`is_closed`](Self::is_closed) or the result of `send`.
+///
+/// ```ignore
+/// #[servify(stream)]
+/// fn tail(&self, from: usize, lines: servify::StreamSender<String>) {
+///     for line in &self.lines[from..] {
+///         if lines.send(line.clone()).is_err() {
+///             break;
+///         }
+///     }
+/// }
+/// ```
+pub struct StreamSender<T> {
+    tx: mpsc::UnboundedSender<T>,
+}
+
+impl<T> StreamSender<T> {
+    pub(crate) fn channel() -> (Self, ResponseStream<T>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let stream = ResponseStream {
+            items: rx,
+            reply: None,
+            result: None,
+        };
+        (Self { tx }, stream)
+    }
+
+    /// Sends an item without waiting for the client to read it. Fails with
+    /// [`Error::Disconnected`] once the client has dropped the stream.
+    pub fn send(&self, item: T) -> Result<(), Error> {
+        self.tx.send(item).map_err(|_| Error::Disconnected)
+    }
+
+    /// Returns whether the client has dropped the stream.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+impl<T> Clone for StreamSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for StreamSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamSender").finish_non_exhaustive()
+    }
+}
+
+type Reply = Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>;
+
+/// The items produced by a `#[servify(stream)]` method, in the order the handler sent them.
+/// The stream ends once the handler and every clone of its [`StreamSender`] are done.
+///
+/// The end of the stream looks the same whether the handler returned or failed. Call
+/// [`finish`](Self::finish) to tell them apart:
+///
+/// ```ignore
+/// let mut lines = client.tail(0).await;
+/// let read: Vec<String> = (&mut lines).collect().await;
+/// lines.finish().await?;
+/// ```
+pub struct ResponseStream<T> {
+    items: mpsc::UnboundedReceiver<T>,
+    // Waits for the handler to return, so the server doesn't see the request as abandoned.
+    reply: Option<Reply>,
+    result: Option<Result<(), Error>>,
+}
+
+impl<T> ResponseStream<T> {
+    pub(crate) fn keep_alive(
+        mut self,
+        reply: impl Future<Output = Result<(), Error>> + Send + 'static,
+    ) -> Self {
+        self.reply = Some(Box::pin(reply));
+        self
+    }
+
+    /// Stops reading, waits for the handler to return and returns how the call ended, e.g.
+    /// [`Error::Panicked`] under `catch_panics` or [`Error::Rejected`] by a middleware.
+    pub async fn finish(mut self) -> Result<(), Error> {
+        self.items.close();
+        while self.next().await.is_some() {}
+        self.result.unwrap_or(Ok(()))
+    }
+}
+
+impl<T> Stream for ResponseStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        match self.items.poll_recv(cx) {
+            Poll::Ready(None) => {}
+            poll => return poll,
+        }
+        if let Some(reply) = &mut self.reply {
+            let Poll::Ready(result) = reply.as_mut().poll(cx) else {
+                return Poll::Pending;
+            };
+            self.reply = None;
+            self.result = Some(result);
+        }
+        Poll::Ready(None)
+    }
+}
+
+impl<T> fmt::Debug for ResponseStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseStream").finish_non_exhaustive()
+    }
+}
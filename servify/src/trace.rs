@@ -0,0 +1,68 @@
+use std::future::Future;
+
+#[cfg(feature = "tracing")]
+use tokio::time::Instant;
+
+/// The span of a single call, opened by the client and carried with the request so the handler
+/// runs inside it. Empty unless the `tracing` feature is enabled.
+#[cfg_attr(not(feature = "tracing"), derive(Default))]
+pub struct CallSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    enqueued: Instant,
+}
+
+impl CallSpan {
+    #[cfg(feature = "tracing")]
+    pub fn new(span: tracing::Span) -> Self {
+        Self {
+            span,
+            enqueued: Instant::now(),
+        }
+    }
+
+    /// Runs the handler inside the span, recording how long the request waited in the queue
+    /// (`queued`) and how long the handler took (`handled`).
+    #[cfg(feature = "tracing")]
+    pub async fn run<F: Future>(self, handler: F) -> F::Output {
+        use tracing::Instrument;
+
+        let started = Instant::now();
+        self.span
+            .record("queued", tracing::field::debug(started - self.enqueued));
+        let output = handler.instrument(self.span.clone()).await;
+        self.span
+            .record("handled", tracing::field::debug(started.elapsed()));
+        output
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub async fn run<F: Future>(self, handler: F) -> F::Output {
+        handler.await
+    }
+}
+
+/// Opens the [`CallSpan`] of a call named `Service::method`, as a child of the current span.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __call_span {
+    ($name:literal) => {
+        $crate::__private::CallSpan::new($crate::__private::tracing::info_span!(
+            $name,
+            queued = $crate::__private::tracing::field::Empty,
+            handled = $crate::__private::tracing::field::Empty,
+        ))
+    };
+}
+
+/// Opens the [`CallSpan`] of a call named `Service::method`, as a child of the current span.
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __call_span {
+    ($name:literal) => {
+        <$crate::__private::CallSpan as ::std::default::Default>::default()
+    };
+}
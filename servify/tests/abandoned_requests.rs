@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+mod patient {
+    use super::*;
+
+    #[servify::service(
+        impls = [
+            PatientCounter_get,
+            PatientCounter_increment_slowly,
+        ]
+    )]
+    struct PatientCounter {
+        pub counter: u32,
+    }
+
+    #[servify::export]
+    impl PatientCounter {
+        fn get(&self) -> u32 {
+            self.counter
+        }
+
+        fn increment_slowly(&mut self, delay: Duration) -> u32 {
+            tokio::time::sleep(delay).await;
+            self.counter += 1;
+            self.counter
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keeps_serving_after_caller_gives_up() {
+        let (counter_rx, counter_client) = PatientCounter::initiate_message_passing(32);
+
+        tokio::spawn(async move {
+            PatientCounter::Server { counter: 0 }
+                .listen(counter_rx)
+                .await;
+        });
+
+        assert_eq!(
+            counter_client
+                .timeout(Duration::from_millis(10))
+                .try_increment_slowly(Duration::from_millis(50))
+                .await,
+            Err(servify::Error::Timeout)
+        );
+        assert_eq!(counter_client.get().await, 1);
+        assert_eq!(counter_client.abandoned_requests(), 1);
+    }
+}
+
+mod skipping {
+    use super::*;
+
+    #[servify::service(
+        impls = [
+            SkippingCounter_get,
+            SkippingCounter_increment_slowly,
+        ],
+        skip_abandoned,
+    )]
+    struct SkippingCounter {
+        pub counter: u32,
+    }
+
+    #[servify::export]
+    impl SkippingCounter {
+        fn get(&self) -> u32 {
+            self.counter
+        }
+
+        fn increment_slowly(&mut self, delay: Duration) -> u32 {
+            tokio::time::sleep(delay).await;
+            self.counter += 1;
+            self.counter
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn skips_requests_abandoned_while_queued() {
+        let (counter_rx, counter_client) = SkippingCounter::initiate_message_passing(32);
+
+        tokio::spawn(async move {
+            SkippingCounter::Server { counter: 0 }
+                .listen(counter_rx)
+                .await;
+        });
+
+        let busy_client = counter_client.clone();
+        let busy = tokio::spawn(async move {
+            busy_client
+                .increment_slowly(Duration::from_millis(50))
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(
+            counter_client
+                .timeout(Duration::from_millis(10))
+                .try_increment_slowly(Duration::ZERO)
+                .await,
+            Err(servify::Error::Timeout)
+        );
+        assert_eq!(busy.await.unwrap(), 1);
+        assert_eq!(counter_client.get().await, 1);
+        assert_eq!(counter_client.abandoned_requests(), 1);
+    }
+}
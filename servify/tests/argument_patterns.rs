@@ -0,0 +1,64 @@
+#[derive(Clone, Copy)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[servify::service(
+    impls = [
+        Plane_move_by,
+        Plane_move_to,
+        Plane_scale,
+        Plane_position,
+    ]
+)]
+struct Plane {
+    pub position: super::Point,
+}
+
+#[servify::export]
+impl Plane {
+    fn move_by(&mut self, (dx, dy): (i32, i32)) -> Point {
+        self.position.x += dx;
+        self.position.y += dy;
+        self.position
+    }
+
+    fn move_to(&mut self, Point { x, y }: Point, _: bool) -> Point {
+        self.position = Point { x, y };
+        self.position
+    }
+
+    fn scale(&mut self, mut factor: i32, [min, max]: [i32; 2]) -> Point {
+        factor = factor.clamp(min, max);
+        self.position.x *= factor;
+        self.position.y *= factor;
+        self.position
+    }
+
+    fn position(&self) -> (i32, i32) {
+        (self.position.x, self.position.y)
+    }
+}
+
+#[tokio::test]
+async fn destructures_arguments() {
+    let (plane_rx, plane_client) = Plane::initiate_message_passing(32);
+
+    tokio::spawn(async move {
+        Plane::Server {
+            position: Point { x: 0, y: 0 },
+        }
+        .listen(plane_rx)
+        .await;
+    });
+
+    plane_client.move_by((1, 2)).await;
+    assert_eq!(plane_client.position().await, (1, 2));
+
+    plane_client.move_to(Point { x: 3, y: 4 }, true).await;
+    assert_eq!(plane_client.position().await, (3, 4));
+
+    plane_client.scale(10, [1, 2]).await;
+    assert_eq!(plane_client.position().await, (6, 8));
+}
@@ -0,0 +1,39 @@
+mod service {
+    /// Counts things.
+    #[servify::service(
+        impls = [
+            Counter_increment,
+        ]
+    )]
+    #[cfg(test)]
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct Counter {
+        pub count: u32,
+    }
+
+    #[servify::export]
+    impl Counter {
+        /// Increments the counter and returns the new count.
+        #[allow(clippy::let_and_return)]
+        fn increment(&mut self) -> u32 {
+            self.count += 1;
+            let count = self.count;
+            count
+        }
+    }
+}
+
+use service::Counter;
+
+#[tokio::test]
+async fn forwards_visibility_and_derives() {
+    let (client, server) =
+        Counter::spawn(Counter::Server { count: 1 }, servify::SpawnConfig::new());
+
+    assert_eq!(client.increment().await, 2);
+
+    drop(client);
+    let server = server.await.unwrap();
+    assert_eq!(server.clone(), Counter::Server { count: 2 });
+    assert_eq!(format!("{:?}", server), "Server { count: 2 }");
+}
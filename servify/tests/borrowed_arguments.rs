@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+
+#[servify::service]
+struct Catalog {
+    pub names: Vec<String>,
+    pub files: Vec<std::path::PathBuf>,
+}
+
+#[servify::service]
+impl Catalog {
+    fn add_name(&mut self, name: &str) -> usize {
+        self.names.push(name.to_string());
+        self.names.len()
+    }
+
+    fn sum(&self, values: &[u32], offset: u32) -> u32 {
+        values.iter().sum::<u32>() + offset
+    }
+
+    fn add_file(&mut self, path: &Path) -> bool {
+        self.files.push(path.to_path_buf());
+        path.is_absolute()
+    }
+
+    fn contains(&self, name: &String) -> bool {
+        self.names.contains(name)
+    }
+}
+
+#[tokio::test]
+async fn accepts_borrowed_arguments() {
+    let (client, server) = Catalog::spawn(
+        Catalog::Server {
+            names: vec![],
+            files: vec![],
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    let name = String::from("servify");
+    assert_eq!(client.add_name(&name).await, 1);
+    assert_eq!(client.add_name("tokio").await, 2);
+    assert!(client.contains(&name).await);
+    assert_eq!(client.sum(&[1, 2, 3], 4).await, 10);
+    let values: Vec<u32> = (1..=4).collect();
+    assert_eq!(client.sum(&values, 0).await, 10);
+    assert!(client.add_file(Path::new("/tmp/servify")).await);
+
+    drop(client);
+    let server = server.await.unwrap();
+    assert_eq!(server.names, vec!["servify", "tokio"]);
+    assert_eq!(server.files, vec![PathBuf::from("/tmp/servify")]);
+}
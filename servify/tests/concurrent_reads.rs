@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use servify::SpawnConfig;
+use tokio::sync::Barrier;
+
+#[servify::service(
+    impls = [
+        SharedCounter_get_together,
+        SharedCounter_increment_slowly,
+    ],
+    concurrent_reads,
+)]
+struct SharedCounter {
+    pub counter: u32,
+    pub readers: std::sync::Arc<tokio::sync::Barrier>,
+}
+
+#[servify::export]
+impl SharedCounter {
+    fn get_together(&self) -> u32 {
+        self.readers.wait().await;
+        self.counter
+    }
+
+    fn increment_slowly(&mut self, delay: Duration) -> u32 {
+        tokio::time::sleep(delay).await;
+        self.counter += 1;
+        self.counter
+    }
+}
+
+fn server(counter: u32, readers: usize) -> SharedCounter::Server {
+    SharedCounter::Server {
+        counter,
+        readers: Arc::new(Barrier::new(readers)),
+    }
+}
+
+#[tokio::test]
+async fn reads_run_concurrently() {
+    let (counter_client, server) = SharedCounter::spawn(server(7, 3), SpawnConfig::new());
+    let counter_client = counter_client.with_timeout(Duration::from_secs(1));
+
+    let reads: Vec<_> = (0..3)
+        .map(|_| {
+            let counter_client = counter_client.clone();
+            tokio::spawn(async move { counter_client.try_get_together().await })
+        })
+        .collect();
+    for read in reads {
+        assert_eq!(read.await.unwrap(), Ok(7));
+    }
+
+    drop(counter_client);
+    assert_eq!(server.await.unwrap().counter, 7);
+}
+
+#[tokio::test]
+async fn writes_stay_exclusive_and_ordered() {
+    let (counter_client, server) = SharedCounter::spawn(server(0, 1), SpawnConfig::new());
+
+    let writes: Vec<_> = (0..3)
+        .map(|_| {
+            let counter_client = counter_client.clone();
+            tokio::spawn(async move {
+                counter_client
+                    .increment_slowly(Duration::from_millis(10))
+                    .await
+            })
+        })
+        .collect();
+    let mut results = vec![];
+    for write in writes {
+        results.push(write.await.unwrap());
+    }
+    results.sort();
+    assert_eq!(results, vec![1, 2, 3]);
+
+    let write = counter_client.increment_slowly(Duration::from_millis(20));
+    let read = counter_client.get_together();
+    assert_eq!(tokio::join!(write, read), (4, 4));
+
+    drop(counter_client);
+    assert_eq!(server.await.unwrap().counter, 4);
+}
+
+#[servify::service(concurrent_reads)]
+#[derive(Debug)]
+struct Fragile {
+    pub n: u32,
+}
+
+#[servify::service]
+impl Fragile {
+    fn get(&self) -> u32 {
+        self.n
+    }
+
+    fn break_halfway(&mut self) {
+        self.n = 99;
+        panic!("broke halfway");
+    }
+}
+
+#[tokio::test]
+async fn handler_panic_stops_server() {
+    let (client, server) = Fragile::spawn(Fragile::Server { n: 1 }, SpawnConfig::new());
+
+    assert_eq!(
+        client.try_break_halfway().await,
+        Err(servify::Error::ServerDropped)
+    );
+    assert!(client.try_get().await.is_err());
+    assert!(server.await.unwrap_err().is_panic());
+}
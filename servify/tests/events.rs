@@ -0,0 +1,96 @@
+use tokio_stream::StreamExt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CounterEvent {
+    Changed(u32),
+    Reset,
+}
+
+#[derive(Clone)]
+pub struct Context<T>(pub T);
+
+#[servify::service(events = CounterEvent)]
+struct Counter {
+    pub count: u32,
+}
+
+#[servify::service]
+impl Counter {
+    fn increment(&mut self, count: u32, ctx: servify::Context<CounterEvent>) -> u32 {
+        self.count += count;
+        ctx.emit(CounterEvent::Changed(self.count));
+        self.count
+    }
+
+    fn reset(&mut self, ctx: servify::Context<CounterEvent>) {
+        self.count = 0;
+        ctx.emit(CounterEvent::Reset);
+    }
+
+    fn get(&self) -> u32 {
+        self.count
+    }
+
+    // A type of the user's named `Context` is sent by the client like any other argument.
+    fn label(&self, context: Context<u32>) -> String {
+        format!("{}: {}", context.0, self.count)
+    }
+}
+
+#[tokio::test]
+async fn subscribers_receive_emitted_events() {
+    let (client, server) =
+        Counter::spawn(Counter::Server { count: 0 }, servify::SpawnConfig::new());
+
+    client.increment(1).await;
+    let events = client.subscribe();
+    let other = client.clone().subscribe();
+
+    client.increment(2).await;
+    client.reset().await;
+    assert_eq!(client.get().await, 0);
+    assert_eq!(client.label(Context(7)).await, "7: 0");
+
+    drop(client);
+    server.await.unwrap();
+
+    let expected = vec![CounterEvent::Changed(3), CounterEvent::Reset];
+    assert_eq!(events.collect::<Vec<_>>().await, expected);
+    assert_eq!(other.collect::<Vec<_>>().await, expected);
+}
+
+#[servify::service(events = String, concurrent_reads, catch_panics)]
+struct Board {
+    pub posts: Vec<String>,
+}
+
+#[servify::service]
+impl Board {
+    fn post(&mut self, text: String, ctx: servify::Context<String>) -> usize {
+        ctx.emit(text.clone());
+        self.posts.push(text);
+        self.posts.len()
+    }
+
+    fn ping(&self, ctx: servify::Context<String>) -> usize {
+        ctx.emit("ping".to_string())
+    }
+}
+
+#[tokio::test]
+async fn concurrent_handlers_emit_events() {
+    let (client, server) =
+        Board::spawn(Board::Server { posts: vec![] }, servify::SpawnConfig::new());
+
+    assert_eq!(client.ping().await, 0);
+    let mut events = client.subscribe();
+    assert_eq!(client.post("hello".to_string()).await, 1);
+    assert_eq!(client.ping().await, 1);
+
+    assert_eq!(events.next().await.as_deref(), Some("hello"));
+    assert_eq!(events.next().await.as_deref(), Some("ping"));
+
+    drop(client);
+    server.await.unwrap();
+    assert_eq!(events.next().await, None);
+}
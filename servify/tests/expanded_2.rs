@@ -18,39 +18,57 @@ mod SomeStruct {
     pub enum Message {
         Increment(
             <SomeStruct_increment as ::servify::ServifyExport>::Request,
-            tokio::sync::oneshot::Sender<
-                <SomeStruct_increment as ::servify::ServifyExport>::Response,
-            >,
+            <SomeStruct_increment as ::servify::ServifyExport>::Responder,
+            ::servify::__private::CallSpan,
         ),
     }
 
-    pub fn initiate_message_passing() -> (::tokio::sync::mpsc::Receiver<Message>, Client) {
-        let (tx, rx) = ::tokio::sync::mpsc::channel(64);
+    pub struct Receiver {
+        messages: ::tokio::sync::mpsc::Receiver<Message>,
+    }
+
+    pub fn initiate_message_passing() -> (Receiver, Client) {
+        let (tx, messages) = ::tokio::sync::mpsc::channel(64);
         let client = Client { tx };
-        (rx, client)
+        (Receiver { messages }, client)
     }
 
     impl Server {
-        pub async fn listen(&mut self, mut rx: ::tokio::sync::mpsc::Receiver<Message>) {
-            while let Some(msg) = rx.recv().await {
+        pub async fn listen(mut self, mut rx: Receiver) -> Self {
+            while let Some(msg) = rx.messages.recv().await {
                 match msg {
-                    Message::Increment(req, tx) => {
-                        let res = self.increment(req).await;
-                        tx.send(res).unwrap();
+                    Message::Increment(req, tx, span) => {
+                        let res = Ok(span.run(self.increment(req)).await);
+                        let _ = ::servify::__private::Responder::send(tx, res);
                     }
                 }
             }
+            self
         }
     }
 
+    #[doc(hidden)]
+    pub async fn __internal_try_increment(
+        client: &Client,
+        req: <SomeStruct_increment as ::servify::ServifyExport>::Request,
+    ) -> Result<<SomeStruct_increment as ::servify::ServifyExport>::Response, ::servify::Error>
+    {
+        let (tx, rx) = <<SomeStruct_increment as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::channel();
+        let span = ::servify::__call_span!("SomeStruct::increment");
+        client
+            .tx
+            .send(Message::Increment(req, tx, span))
+            .await
+            .map_err(|_| ::servify::Error::Disconnected)?;
+        rx.await.map_err(|_| ::servify::Error::ServerDropped)?
+    }
+
     #[doc(hidden)]
     pub async fn __internal_increment(
         client: &Client,
         req: <SomeStruct_increment as ::servify::ServifyExport>::Request,
     ) -> <SomeStruct_increment as ::servify::ServifyExport>::Response {
-        let (tx, rx) = ::tokio::sync::oneshot::channel();
-        client.tx.send(Message::Increment(req, tx)).await.unwrap();
-        rx.await.unwrap()
+        __internal_try_increment(client, req).await.unwrap()
     }
 }
 
@@ -87,6 +105,8 @@ mod some_other {
     impl ::servify::ServifyExport for SomeStruct_increment {
         type Request = __increment_request;
         type Response = __increment_response;
+        type Responder =
+            ::tokio::sync::oneshot::Sender<Result<__increment_response, ::servify::Error>>;
     }
 }
 
@@ -0,0 +1,52 @@
+#[servify::service(
+    impls = [
+        FallibleCounter_get,
+        FallibleCounter_explode,
+    ]
+)]
+struct FallibleCounter {
+    pub counter: u32,
+}
+
+#[servify::export]
+impl FallibleCounter {
+    fn get(&self) -> u32 {
+        self.counter
+    }
+
+    fn explode(&mut self) {
+        panic!("the handler exploded");
+    }
+}
+
+#[tokio::test]
+async fn disconnected_when_server_is_gone() {
+    let (counter_rx, counter_client) = FallibleCounter::initiate_message_passing(32);
+    drop(counter_rx);
+
+    assert_eq!(
+        counter_client.try_get().await,
+        Err(servify::Error::Disconnected)
+    );
+}
+
+#[tokio::test]
+async fn server_dropped_when_handler_panics() {
+    let (counter_rx, counter_client) = FallibleCounter::initiate_message_passing(32);
+
+    tokio::spawn(async move {
+        FallibleCounter::Server { counter: 5 }
+            .listen(counter_rx)
+            .await;
+    });
+
+    assert_eq!(counter_client.try_get().await, Ok(5));
+    assert_eq!(
+        counter_client.try_explode().await,
+        Err(servify::Error::ServerDropped)
+    );
+    assert_eq!(
+        counter_client.try_get().await,
+        Err(servify::Error::Disconnected)
+    );
+}
@@ -0,0 +1,83 @@
+mod cache {
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    #[servify::service(
+        impls = [
+            Cache_insert,
+            Cache_get,
+        ],
+        concurrent_reads,
+    )]
+    struct Cache<K: std::hash::Hash + Eq, V: Clone> {
+        pub entries: std::collections::HashMap<K, V>,
+    }
+
+    #[servify::export]
+    impl<K: Hash + Eq, V: Clone> Cache<K, V> {
+        fn insert(&mut self, key: K, value: V) -> Option<V> {
+            self.entries.insert(key, value)
+        }
+
+        fn get(&self, key: K) -> Option<V> {
+            self.entries.get(&key).cloned()
+        }
+    }
+
+    #[tokio::test]
+    async fn threads_generics_through_the_service() {
+        let (client, server) = Cache::spawn(
+            Cache::Server {
+                entries: HashMap::new(),
+            },
+            servify::SpawnConfig::new(),
+        );
+
+        assert_eq!(client.insert("one", 1).await, None);
+        assert_eq!(client.insert("one", 2).await, Some(1));
+        assert_eq!(client.clone().get("one").await, Some(2));
+        assert_eq!(client.get("two").await, None);
+
+        drop(client);
+        assert_eq!(server.await.unwrap().entries.len(), 1);
+    }
+}
+
+mod stack {
+    #[servify::service]
+    struct Stack<T>
+    where
+        T: Clone,
+    {
+        pub items: Vec<T>,
+    }
+
+    #[servify::service]
+    impl<T> Stack<T>
+    where
+        T: Clone,
+    {
+        fn push(&mut self, item: T) -> usize {
+            self.items.push(item);
+            self.items.len()
+        }
+
+        fn peek(&self) -> Option<T> {
+            self.items.last().cloned()
+        }
+    }
+
+    #[tokio::test]
+    async fn supports_where_clauses() {
+        let (rx, client) = Stack::initiate_message_passing::<String>(32);
+
+        tokio::spawn(async move {
+            Stack::Server { items: vec![] }.listen(rx).await;
+        });
+
+        assert_eq!(client.peek().await, None);
+        assert_eq!(client.push("a".to_string()).await, 1);
+        assert_eq!(client.push("b".to_string()).await, 2);
+        assert_eq!(client.peek().await, Some("b".to_string()));
+    }
+}
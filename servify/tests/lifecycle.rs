@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+#[servify::service]
+struct Journal {
+    pub events: Vec<String>,
+    pub pending: Vec<String>,
+}
+
+#[servify::service]
+impl Journal {
+    fn write(&mut self, entry: String) {
+        self.pending.push(entry);
+    }
+
+    fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    #[servify(on_start)]
+    fn open(&mut self) {
+        self.events.push("open".to_string());
+    }
+
+    #[servify(on_idle(after = "50ms"))]
+    fn flush(&mut self) {
+        let flushed = std::mem::take(&mut self.pending).join(",");
+        self.events.push(format!("flush {}", flushed));
+    }
+
+    #[servify(on_stop)]
+    fn close(&mut self) {
+        self.events.push("close".to_string());
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn hooks_run_around_messages() {
+    let (client, server) = Journal::spawn(
+        Journal::Server {
+            events: vec![],
+            pending: vec![],
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    client.write("a".to_string()).await;
+    client.write("b".to_string()).await;
+    assert_eq!(client.pending().await, 2);
+
+    // Idle once, no matter how long the server stays quiet.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(client.pending().await, 0);
+    client.write("c".to_string()).await;
+
+    drop(client);
+    let server = server.await.unwrap();
+    assert_eq!(server.events, vec!["open", "flush a,b", "close"]);
+    assert_eq!(server.pending, vec!["c"]);
+}
+
+#[servify::service(concurrent_reads, on_start = start, on_stop = stop)]
+struct Registry {
+    pub started: bool,
+    pub stopped: bool,
+}
+
+#[servify::service]
+impl Registry {
+    fn started(&self) -> bool {
+        self.started
+    }
+}
+
+impl Registry::Server {
+    async fn start(&mut self) {
+        self.started = true;
+    }
+
+    async fn stop(&mut self) {
+        self.stopped = true;
+    }
+}
+
+#[tokio::test]
+async fn hooks_given_on_struct() {
+    let (client, server) = Registry::spawn(
+        Registry::Server {
+            started: false,
+            stopped: false,
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    assert!(client.started().await);
+
+    drop(client);
+    assert!(server.await.unwrap().stopped);
+}
@@ -0,0 +1,82 @@
+mod listed {
+    #[servify::service(
+        impls = [
+            Thermostat_set,
+            Thermostat_current,
+        ]
+    )]
+    struct Thermostat {
+        pub target: i32,
+    }
+
+    #[servify::export]
+    impl Thermostat {
+        const MIN: i32 = 10;
+        const MAX: i32 = 30;
+
+        #[servify(rename = "set")]
+        fn set_target(&mut self, target: i32) -> i32 {
+            self.target = Self::clamp(target);
+            self.target
+        }
+
+        #[servify(rename = "current")]
+        fn target(&self) -> i32 {
+            self.target
+        }
+
+        #[servify(skip)]
+        fn clamp(target: i32) -> i32 {
+            target.clamp(Self::MIN, Self::MAX)
+        }
+    }
+
+    #[tokio::test]
+    async fn renames_and_skips() {
+        let (client, server) = Thermostat::spawn(
+            Thermostat::Server { target: 20 },
+            servify::SpawnConfig::new(),
+        );
+
+        assert_eq!(client.current().await, 20);
+        assert_eq!(client.set(45).await, 30);
+        assert_eq!(client.try_set(0).await, Ok(10));
+
+        drop(client);
+        let server = server.await.unwrap();
+        assert_eq!(server.target, 10);
+        assert_eq!(Thermostat::Server::clamp(-5), Thermostat::Server::MIN);
+    }
+}
+
+mod discovered {
+    #[servify::service]
+    struct Greeter {
+        pub greeting: String,
+    }
+
+    #[servify::service]
+    impl Greeter {
+        #[servify(rename = "greet")]
+        fn greet_by_name(&self, name: String) -> String {
+            self.format(&name)
+        }
+
+        #[servify(skip)]
+        pub fn format(&self, name: &str) -> String {
+            format!("{}, {}!", self.greeting, name)
+        }
+    }
+
+    #[tokio::test]
+    async fn renames_and_skips() {
+        let (client, _server) = Greeter::spawn(
+            Greeter::Server {
+                greeting: "Hello".to_string(),
+            },
+            servify::SpawnConfig::new(),
+        );
+
+        assert_eq!(client.greet("servify".to_string()).await, "Hello, servify!");
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex};
+
+use servify::{Call, Completed, Error, Middleware};
+
+/// Records every call, and refuses to add names containing "admin".
+#[derive(Clone, Default)]
+pub struct Audit {
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Audit {
+    fn before(&self, call: &Call<'_>) -> Result<(), Error> {
+        let request = format!("{:?}", call.request());
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", call.method(), request));
+        if request.contains("admin") {
+            return Err(Error::Rejected("reserved name".to_string()));
+        }
+        Ok(())
+    }
+
+    fn after(&self, completed: &Completed<'_>) {
+        let response = format!("{:?}", completed.response());
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} -> {}", completed.method(), response));
+    }
+}
+
+#[servify::service]
+struct Users {
+    pub names: Vec<String>,
+}
+
+#[servify::service(derive(Debug))]
+impl Users {
+    fn add(&mut self, name: String) -> usize {
+        self.names.push(name);
+        self.names.len()
+    }
+
+    #[servify(rename = "count")]
+    fn len(&self) -> Option<usize> {
+        Some(self.names.len())
+    }
+}
+
+#[tokio::test]
+async fn middleware_runs_around_handlers() {
+    let audit = Audit::default();
+    let (client, server) = Users::spawn(
+        Users::Server { names: vec![] },
+        servify::SpawnConfig::new().middleware(audit.clone()),
+    );
+
+    assert_eq!(client.add("alice".to_string()).await, 1);
+    assert_eq!(
+        client.try_add("admin".to_string()).await,
+        Err(Error::Rejected("reserved name".to_string()))
+    );
+    assert_eq!(client.count().await, Some(1));
+
+    drop(client);
+    assert_eq!(server.await.unwrap().names, vec!["alice"]);
+    assert_eq!(
+        *audit.log.lock().unwrap(),
+        vec![
+            r#"add Some(__Users_add_request { name: "alice" })"#,
+            "add -> Ok(Some(1))",
+            r#"add Some(__Users_add_request { name: "admin" })"#,
+            "count Some(__Users_count_request)",
+            "count -> Ok(Some(Some(1)))",
+        ]
+    );
+}
+
+static TIMINGS: Mutex<Vec<&'static str>> = Mutex::new(vec![]);
+
+#[derive(Default)]
+pub struct Timings;
+
+impl Middleware for Timings {
+    fn after(&self, completed: &Completed<'_>) {
+        assert!(completed.response().unwrap().is_none());
+        TIMINGS.lock().unwrap().push(completed.method());
+    }
+}
+
+pub struct Opaque(pub u32);
+
+#[servify::service(middleware = Timings, concurrent_reads)]
+struct Store {
+    pub value: u32,
+}
+
+#[servify::service]
+impl Store {
+    fn get(&self) -> Opaque {
+        Opaque(self.value)
+    }
+}
+
+#[tokio::test]
+async fn middleware_without_debug() {
+    let (client, server) = Store::spawn(Store::Server { value: 3 }, servify::SpawnConfig::new());
+
+    assert_eq!(client.get().await.0, 3);
+
+    drop(client);
+    server.await.unwrap();
+    assert_eq!(*TIMINGS.lock().unwrap(), vec!["get"]);
+}
+
+#[tokio::test]
+async fn given_middleware_replaces_declared_one() {
+    let audit = Audit::default();
+    let (rx, client) = Store::initiate_message_passing(8);
+    let server = tokio::spawn(Store::Server { value: 5 }.listen(rx.with_middleware(audit.clone())));
+
+    assert_eq!(client.get().await.0, 5);
+
+    drop(client);
+    server.await.unwrap();
+    assert_eq!(
+        *audit.log.lock().unwrap(),
+        vec!["get None", "get -> Ok(None)"]
+    );
+}
@@ -1,5 +1,29 @@
+mod abandoned_requests;
+mod argument_patterns;
+mod attributes;
+mod borrowed_arguments;
+mod concurrent_reads;
+mod events;
 mod expanded_1;
 mod expanded_2;
+mod fallible_client;
+mod generics;
+mod lifecycle;
+mod method_options;
+mod middleware;
+mod oneway;
+mod panic_isolation;
+mod request_derives;
+mod scheduled;
+mod shared_module;
+mod shutdown;
 mod simple_counter;
 mod simple_counter_2;
 mod simple_counter_file_split;
+mod single_attribute;
+mod spawn;
+mod streaming;
+mod struct_shapes;
+mod ticks;
+mod timeout;
+mod tracing;
@@ -0,0 +1,54 @@
+#[servify::service]
+struct Metrics {
+    pub hits: u64,
+}
+
+#[servify::service]
+impl Metrics {
+    #[servify(oneway)]
+    fn hit(&mut self, count: u64) {
+        self.hits += count;
+    }
+
+    #[servify(oneway)]
+    fn reset(&mut self) -> () {
+        self.hits = 0;
+    }
+
+    fn hits(&self) -> u64 {
+        self.hits
+    }
+}
+
+#[tokio::test]
+async fn oneway_methods_are_handled_in_order() {
+    let (client, server) = Metrics::spawn(Metrics::Server { hits: 0 }, servify::SpawnConfig::new());
+
+    for _ in 0..10 {
+        client.hit(2).await;
+    }
+    assert_eq!(client.hits().await, 20);
+
+    client.try_reset().await.unwrap();
+    client.hit(1).await;
+    assert_eq!(client.hits().await, 1);
+    assert_eq!(client.abandoned_requests(), 0);
+
+    drop(client);
+    assert_eq!(server.await.unwrap().hits, 1);
+}
+
+#[tokio::test]
+async fn oneway_methods_return_before_handling() {
+    let (rx, client) = Metrics::initiate_message_passing(4);
+
+    // Nobody is listening yet, but the messages fit in the buffer.
+    client.hit(3).await;
+    client.hit(4).await;
+
+    let server = tokio::spawn(Metrics::Server { hits: 0 }.listen(rx));
+    assert_eq!(client.hits().await, 7);
+
+    drop(client);
+    assert_eq!(server.await.unwrap().hits, 7);
+}
@@ -0,0 +1,133 @@
+mod keep_serving {
+    #[servify::service(
+        impls = [
+            FragileCounter_increment,
+            FragileCounter_explode,
+        ],
+        catch_panics,
+    )]
+    struct FragileCounter {
+        pub counter: u32,
+    }
+
+    #[servify::export]
+    impl FragileCounter {
+        fn increment(&mut self) -> u32 {
+            self.counter += 1;
+            self.counter
+        }
+
+        fn explode(&mut self) {
+            panic!("the handler exploded");
+        }
+    }
+
+    #[tokio::test]
+    async fn continues_after_panic() {
+        let (counter_rx, counter_client) = FragileCounter::initiate_message_passing(32);
+
+        tokio::spawn(async move {
+            FragileCounter::Server { counter: 0 }
+                .listen(counter_rx)
+                .await;
+        });
+
+        assert_eq!(counter_client.increment().await, 1);
+        assert_eq!(
+            counter_client.try_explode().await,
+            Err(servify::Error::Panicked("the handler exploded".to_string()))
+        );
+        assert_eq!(counter_client.increment().await, 2);
+    }
+}
+
+mod stop {
+    #[servify::service(
+        impls = [
+            FragileCounter_increment,
+            FragileCounter_explode,
+        ],
+        catch_panics(stop),
+    )]
+    struct FragileCounter {
+        pub counter: u32,
+    }
+
+    #[servify::export]
+    impl FragileCounter {
+        fn increment(&mut self) -> u32 {
+            self.counter += 1;
+            self.counter
+        }
+
+        fn explode(&mut self) {
+            panic!("the handler exploded");
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_after_panic() {
+        let (counter_rx, counter_client) = FragileCounter::initiate_message_passing(32);
+
+        let server = tokio::spawn(async move {
+            FragileCounter::Server { counter: 0 }
+                .listen(counter_rx)
+                .await
+        });
+
+        assert_eq!(counter_client.increment().await, 1);
+        assert_eq!(
+            counter_client.try_explode().await,
+            Err(servify::Error::Panicked("the handler exploded".to_string()))
+        );
+        assert_eq!(server.await.unwrap().counter, 1);
+        assert_eq!(
+            counter_client.try_increment().await,
+            Err(servify::Error::Disconnected)
+        );
+    }
+}
+
+mod restart {
+    #[servify::service(
+        impls = [
+            FragileCounter_increment,
+            FragileCounter_explode,
+        ],
+        catch_panics(restart = fresh_counter),
+    )]
+    struct FragileCounter {
+        pub counter: u32,
+    }
+
+    fn fresh_counter() -> FragileCounter::Server {
+        FragileCounter::Server { counter: 100 }
+    }
+
+    #[servify::export]
+    impl FragileCounter {
+        fn increment(&mut self) -> u32 {
+            self.counter += 1;
+            self.counter
+        }
+
+        fn explode(&mut self) {
+            panic!("the handler exploded");
+        }
+    }
+
+    #[tokio::test]
+    async fn restarts_from_factory_after_panic() {
+        let (counter_rx, counter_client) = FragileCounter::initiate_message_passing(32);
+
+        tokio::spawn(async move {
+            FragileCounter::Server { counter: 0 }
+                .listen(counter_rx)
+                .await;
+        });
+
+        assert_eq!(counter_client.increment().await, 1);
+        assert!(counter_client.try_explode().await.is_err());
+        assert_eq!(counter_client.increment().await, 101);
+    }
+}
@@ -0,0 +1,68 @@
+use servify::ServifyExport;
+
+mod listed {
+    #[servify::service(impls = [Ledger_record])]
+    pub struct Ledger {
+        pub entries: Vec<(String, i64)>,
+    }
+
+    #[servify::export(derive(Debug, PartialEq))]
+    impl Ledger {
+        fn record(&mut self, account: String, amount: i64) -> usize {
+            self.entries.push((account, amount));
+            self.entries.len()
+        }
+    }
+}
+
+mod discovered {
+    #[servify::service]
+    pub struct Tally {
+        pub total: u64,
+    }
+
+    #[servify::service(derive(Debug, Default, Hash))]
+    impl Tally {
+        fn add(&mut self, amount: u64) -> u64 {
+            self.total += amount;
+            self.total
+        }
+    }
+}
+
+type RecordRequest = <listed::Ledger_record as ServifyExport>::Request;
+type AddRequest = <discovered::Tally_add as ServifyExport>::Request;
+
+#[test]
+fn requests_use_configured_derives() {
+    let request = RecordRequest::new("rent".to_string(), -500);
+    assert_eq!(request.account, "rent");
+    assert_eq!(request.amount, -500);
+    assert_eq!(request.clone(), request);
+    assert_eq!(
+        format!("{:?}", request),
+        r#"__Ledger_record_request { account: "rent", amount: -500 }"#
+    );
+
+    let request = AddRequest::default();
+    assert_eq!(request.amount, 0);
+    assert_eq!(
+        format!("{:?}", AddRequest::new(3)),
+        "__Tally_add_request { amount: 3 }"
+    );
+}
+
+#[tokio::test]
+async fn services_still_serve_requests() {
+    let (ledger, _ledger_server) = listed::Ledger::spawn(
+        listed::Ledger::Server { entries: vec![] },
+        servify::SpawnConfig::new(),
+    );
+    let (tally, _tally_server) = discovered::Tally::spawn(
+        discovered::Tally::Server { total: 0 },
+        servify::SpawnConfig::new(),
+    );
+
+    assert_eq!(ledger.record("rent".to_string(), -500).await, 1);
+    assert_eq!(tally.add(2).await, 2);
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use servify::Error;
+use tokio::time::Instant;
+
+#[servify::service]
+struct Session {
+    pub name: String,
+    pub resets: u32,
+}
+
+#[servify::service]
+impl Session {
+    fn rename(&mut self, name: &str) -> String {
+        std::mem::replace(&mut self.name, name.to_string())
+    }
+
+    fn reset(&mut self) -> u32 {
+        self.resets += 1;
+        self.resets
+    }
+
+    fn resets(&self) -> u32 {
+        self.resets
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn delivers_scheduled_calls_later() {
+    let (client, server) = Session::spawn(
+        Session::Server {
+            name: "first".to_string(),
+            resets: 0,
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    let reset = client.after(Duration::from_millis(50)).reset();
+    let rename = client
+        .at(Instant::now() + Duration::from_millis(50))
+        .rename("second");
+    assert_eq!(client.resets().await, 0);
+
+    assert_eq!(reset.await, Ok(1));
+    assert_eq!(rename.await.as_deref(), Ok("first"));
+    assert_eq!(client.resets().await, 1);
+
+    drop(client);
+    assert_eq!(server.await.unwrap().name, "second");
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancels_scheduled_calls() {
+    let (client, server) = Session::spawn(
+        Session::Server {
+            name: "first".to_string(),
+            resets: 0,
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    let reset = client.after(Duration::from_millis(50)).reset();
+    reset.cancel();
+    assert_eq!(reset.await, Err(Error::Cancelled));
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(client.resets().await, 0);
+
+    drop(client);
+    server.await.unwrap();
+}
@@ -0,0 +1,47 @@
+#[servify::service(impls = [Counter_get, Counter_set])]
+struct Counter {
+    pub count: u32,
+}
+
+#[servify::export]
+impl Counter {
+    fn get(&self) -> u32 {
+        self.count
+    }
+    fn set(&mut self, count: u32) {
+        self.count = count;
+    }
+}
+
+#[servify::service]
+struct Label {
+    pub text: String,
+}
+
+#[servify::service]
+impl Label {
+    fn get(&self) -> String {
+        self.text.clone()
+    }
+    fn set(&mut self, text: String) {
+        self.text = text;
+    }
+}
+
+#[tokio::test]
+async fn services_share_a_module() {
+    let (counter, _counter_server) =
+        Counter::spawn(Counter::Server { count: 1 }, servify::SpawnConfig::new());
+    let (label, _label_server) = Label::spawn(
+        Label::Server {
+            text: "one".to_string(),
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    counter.set(2).await;
+    label.set("two".to_string()).await;
+
+    assert_eq!(counter.get().await, 2);
+    assert_eq!(label.get().await, "two");
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use servify::ShutdownMode;
+
+#[servify::service(
+    impls = [
+        DurableCounter_increment,
+        DurableCounter_increment_slowly,
+    ]
+)]
+struct DurableCounter {
+    pub counter: u32,
+}
+
+#[servify::export]
+impl DurableCounter {
+    fn increment(&mut self) -> u32 {
+        self.counter += 1;
+        self.counter
+    }
+
+    fn increment_slowly(&mut self, delay: Duration) -> u32 {
+        tokio::time::sleep(delay).await;
+        self.counter += 1;
+        self.counter
+    }
+}
+
+async fn shutdown_while_busy(
+    mode: ShutdownMode,
+) -> (Vec<Result<u32, servify::Error>>, DurableCounter::Server) {
+    let (counter_rx, counter_client) = DurableCounter::initiate_message_passing(32);
+    let shutdown = counter_rx.shutdown_handle();
+
+    let server = tokio::spawn(async move {
+        DurableCounter::Server { counter: 0 }
+            .listen(counter_rx)
+            .await
+    });
+
+    let busy_client = counter_client.clone();
+    let busy = tokio::spawn(async move {
+        busy_client
+            .try_increment_slowly(Duration::from_millis(50))
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let queued: Vec<_> = (0..2)
+        .map(|_| {
+            let counter_client = counter_client.clone();
+            tokio::spawn(async move { counter_client.try_increment().await })
+        })
+        .collect();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    shutdown.shutdown(mode);
+
+    let mut results = vec![busy.await.unwrap()];
+    for call in queued {
+        results.push(call.await.unwrap());
+    }
+    let server = server.await.unwrap();
+
+    assert_eq!(
+        counter_client.try_increment().await,
+        Err(servify::Error::Disconnected)
+    );
+    (results, server)
+}
+
+#[tokio::test(start_paused = true)]
+async fn drain_handles_queued_messages() {
+    let (results, server) = shutdown_while_busy(ShutdownMode::Drain).await;
+
+    let mut replies: Vec<u32> = results.into_iter().map(Result::unwrap).collect();
+    replies.sort();
+    assert_eq!(replies, vec![1, 2, 3]);
+    assert_eq!(server.counter, 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn reject_replies_to_queued_messages() {
+    let (results, server) = shutdown_while_busy(ShutdownMode::Reject).await;
+
+    assert_eq!(
+        results,
+        vec![
+            Ok(1),
+            Err(servify::Error::ShuttingDown),
+            Err(servify::Error::ShuttingDown),
+        ]
+    );
+    assert_eq!(server.counter, 1);
+}
+
+#[tokio::test]
+async fn listen_returns_state_when_clients_are_dropped() {
+    let (counter_rx, counter_client) = DurableCounter::initiate_message_passing(32);
+
+    let server = tokio::spawn(async move {
+        DurableCounter::Server { counter: 0 }
+            .listen(counter_rx)
+            .await
+    });
+
+    assert_eq!(counter_client.increment().await, 1);
+    drop(counter_client);
+
+    assert_eq!(server.await.unwrap().counter, 1);
+}
+
+#[tokio::test]
+async fn spawned_server_stops_through_config_handle() {
+    let config = servify::SpawnConfig::new();
+    let shutdown = config.shutdown_handle();
+    let (counter_client, server) =
+        DurableCounter::spawn(DurableCounter::Server { counter: 0 }, config);
+
+    assert_eq!(counter_client.increment().await, 1);
+    shutdown.drain();
+
+    assert_eq!(server.await.unwrap().counter, 1);
+    assert_eq!(
+        counter_client.try_increment().await,
+        Err(servify::Error::Disconnected)
+    );
+}
@@ -0,0 +1,66 @@
+mod counter {
+    #[servify::service]
+    struct Counter {
+        pub count: u32,
+    }
+
+    #[servify::service]
+    impl Counter {
+        fn increment_and_get(&mut self, count: u32) -> u32 {
+            self.count += count;
+            self.count
+        }
+        fn get_value(&self) -> u32 {
+            self.count
+        }
+    }
+
+    #[tokio::test]
+    async fn count_up() {
+        let (rx, client) = Counter::initiate_message_passing(32);
+
+        tokio::spawn(async move {
+            Counter::Server { count: 3 }.listen(rx).await;
+        });
+
+        assert_eq!(client.get_value().await, 3);
+        assert_eq!(client.increment_and_get(5).await, 8);
+        assert_eq!(client.get_value().await, 8);
+    }
+}
+
+mod with_options {
+    #[servify::service]
+    impl Accumulator {
+        fn add(&mut self, value: u64) -> u64 {
+            self.total = self.total.checked_add(value).expect("total overflowed");
+            self.total
+        }
+        fn total(&self) -> u64 {
+            self.total
+        }
+    }
+
+    #[servify::service(catch_panics, concurrent_reads)]
+    struct Accumulator {
+        pub total: u64,
+    }
+
+    #[tokio::test]
+    async fn forwards_options() {
+        let (client, server) = Accumulator::spawn(
+            Accumulator::Server { total: 0 },
+            servify::SpawnConfig::new(),
+        );
+
+        assert_eq!(client.add(2).await, 2);
+        assert!(matches!(
+            client.try_add(u64::MAX).await,
+            Err(servify::Error::Panicked(_))
+        ));
+        assert_eq!(client.total().await, 2);
+
+        drop(client);
+        assert_eq!(server.await.unwrap().total, 2);
+    }
+}
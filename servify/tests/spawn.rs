@@ -0,0 +1,58 @@
+use servify::SpawnConfig;
+
+#[servify::service(
+    impls = [
+        SpawnedCounter_increment_and_get,
+        SpawnedCounter_get,
+    ]
+)]
+struct SpawnedCounter {
+    pub counter: u32,
+}
+
+#[servify::export]
+impl SpawnedCounter {
+    fn increment_and_get(&mut self) -> u32 {
+        self.counter += 1;
+        self.counter
+    }
+
+    fn get(&self) -> u32 {
+        self.counter
+    }
+}
+
+#[tokio::test]
+async fn spawn_with_default_config() {
+    let (counter_client, server) =
+        SpawnedCounter::spawn(SpawnedCounter::Server { counter: 0 }, SpawnConfig::new());
+
+    assert_eq!(counter_client.increment_and_get().await, 1);
+    assert_eq!(counter_client.get().await, 1);
+
+    drop(counter_client);
+    assert_eq!(server.await.unwrap().counter, 1);
+}
+
+#[tokio::test]
+async fn spawn_with_custom_config() {
+    let config = SpawnConfig::new()
+        .buffer(1)
+        .name("spawned-counter")
+        .runtime(tokio::runtime::Handle::current());
+    let (counter_client, server) =
+        SpawnedCounter::spawn(SpawnedCounter::Server { counter: 10 }, config);
+
+    let calls: Vec<_> = (0..5)
+        .map(|_| {
+            let counter_client = counter_client.clone();
+            tokio::spawn(async move { counter_client.increment_and_get().await })
+        })
+        .collect();
+    for call in calls {
+        call.await.unwrap();
+    }
+
+    drop(counter_client);
+    assert_eq!(server.await.unwrap().counter, 15);
+}
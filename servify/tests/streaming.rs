@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use servify::{Error, StreamSender};
+use tokio_stream::StreamExt;
+
+#[servify::service]
+struct Log {
+    pub lines: Vec<String>,
+}
+
+#[servify::service]
+impl Log {
+    fn append(&mut self, line: String) -> usize {
+        self.lines.push(line);
+        self.lines.len()
+    }
+
+    /// Streams the lines starting at `from`.
+    #[servify(stream)]
+    fn tail(&self, from: usize, lines: StreamSender<String>) {
+        for line in self.lines.iter().skip(from) {
+            if lines.send(line.clone()).is_err() {
+                break;
+            }
+        }
+    }
+
+    #[servify(stream)]
+    fn count_to(&self, to: u32, sender: servify::StreamSender<u32>) {
+        for n in 1..=to {
+            sender.send(n).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn streams_items_from_handler() {
+    let (client, server) = Log::spawn(Log::Server { lines: vec![] }, servify::SpawnConfig::new());
+
+    for line in ["a", "b", "c"] {
+        client.append(line.to_string()).await;
+    }
+
+    let lines: Vec<String> = client.tail(1).await.collect().await;
+    assert_eq!(lines, vec!["b", "c"]);
+
+    let mut numbers = client.try_count_to(5).await.unwrap();
+    let read: Vec<u32> = (&mut numbers).collect().await;
+    assert_eq!(read, vec![1, 2, 3, 4, 5]);
+    assert_eq!(numbers.finish().await, Ok(()));
+
+    assert_eq!(client.append("d".to_string()).await, 4);
+    assert_eq!(client.abandoned_requests(), 0);
+
+    drop(client);
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn unread_stream_does_not_block_server() {
+    let lines = (0..100).map(|n| n.to_string()).collect();
+    let (client, server) = Log::spawn(Log::Server { lines }, servify::SpawnConfig::new());
+
+    let unread = client.tail(0).await;
+    let appended = tokio::time::timeout(Duration::from_secs(1), client.append("end".to_string()));
+    assert_eq!(appended.await, Ok(101));
+
+    let first: Vec<String> = unread.take(2).collect().await;
+    assert_eq!(first, vec!["0", "1"]);
+
+    drop(client);
+    server.await.unwrap();
+}
+
+#[servify::service(catch_panics)]
+struct Flaky {}
+
+#[servify::service]
+impl Flaky {
+    #[servify(stream)]
+    fn numbers(&self, fail_after: u32, sender: StreamSender<u32>) {
+        for n in 1..=3 {
+            if n > fail_after {
+                panic!("ran out of numbers");
+            }
+            if sender.send(n).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn finish_reports_handler_failure() {
+    let (client, server) = Flaky::spawn(Flaky::Server {}, servify::SpawnConfig::new());
+
+    let mut numbers = client.numbers(1).await;
+    let read: Vec<u32> = (&mut numbers).collect().await;
+    assert_eq!(read, vec![1]);
+    assert_eq!(
+        numbers.finish().await,
+        Err(Error::Panicked("ran out of numbers".to_string()))
+    );
+
+    // Finishing early drops the items that were not read yet.
+    assert_eq!(client.numbers(3).await.finish().await, Ok(()));
+
+    drop(client);
+    server.await.unwrap();
+}
@@ -0,0 +1,59 @@
+mod tuple {
+    #[servify::service(
+        impls = [
+            Counter_increment_and_get,
+            Counter_get_value,
+        ]
+    )]
+    struct Counter(pub u32, pub u32);
+
+    #[servify::export]
+    impl Counter {
+        fn increment_and_get(&mut self, count: u32) -> u32 {
+            self.0 += count;
+            self.1 += 1;
+            self.0
+        }
+        fn get_value(&self) -> (u32, u32) {
+            (self.0, self.1)
+        }
+    }
+
+    #[tokio::test]
+    async fn tuple_struct() {
+        let (client, server) = Counter::spawn(Counter::Server(3, 0), servify::SpawnConfig::new());
+
+        assert_eq!(client.increment_and_get(5).await, 8);
+        assert_eq!(client.get_value().await, (8, 1));
+
+        drop(client);
+        assert_eq!(server.await.unwrap().0, 8);
+    }
+}
+
+mod unit {
+    #[servify::service]
+    struct Calculator;
+
+    #[servify::service]
+    impl Calculator {
+        fn add(&self, a: i64, b: i64) -> i64 {
+            a + b
+        }
+        fn negate(&self, a: i64) -> i64 {
+            -a
+        }
+    }
+
+    #[tokio::test]
+    async fn unit_struct() {
+        let (rx, client) = Calculator::initiate_message_passing(32);
+
+        tokio::spawn(async move {
+            Calculator::Server.listen(rx).await;
+        });
+
+        assert_eq!(client.add(2, 3).await, 5);
+        assert_eq!(client.negate(7).await, -7);
+    }
+}
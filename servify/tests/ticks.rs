@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+#[servify::service]
+struct Cache {
+    pub entries: Vec<(String, u32)>,
+    pub sweeps: u32,
+}
+
+#[servify::service]
+impl Cache {
+    fn insert(&mut self, key: String, ttl: u32) {
+        self.entries.push((key, ttl));
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.entries.iter().map(|(key, _)| key.clone()).collect()
+    }
+
+    #[servify(every = "50ms")]
+    fn expire(&mut self) {
+        self.entries.retain_mut(|(_, ttl)| {
+            *ttl = ttl.saturating_sub(1);
+            *ttl > 0
+        });
+    }
+
+    #[servify(every = "1h")]
+    fn sweep(&mut self) {
+        self.sweeps += 1;
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn ticks_run_alongside_messages() {
+    let (client, server) = Cache::spawn(
+        Cache::Server {
+            entries: vec![],
+            sweeps: 0,
+        },
+        servify::SpawnConfig::new(),
+    );
+
+    client.insert("short".to_string(), 1).await;
+    client.insert("long".to_string(), 1000).await;
+    assert_eq!(client.keys().await, vec!["short", "long"]);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert_eq!(client.keys().await, vec!["long"]);
+
+    drop(client);
+    assert_eq!(server.await.unwrap().sweeps, 0);
+}
+
+#[servify::service(concurrent_reads, tick(bump, every = "10ms"))]
+struct Clock {
+    pub ticks: u32,
+}
+
+#[servify::service]
+impl Clock {
+    fn ticks(&self) -> u32 {
+        self.ticks
+    }
+}
+
+impl Clock::Server {
+    async fn bump(&mut self) {
+        self.ticks += 1;
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn ticks_given_on_struct() {
+    let (client, server) = Clock::spawn(Clock::Server { ticks: 0 }, servify::SpawnConfig::new());
+
+    // Ticks fall every 10ms from 10ms on, so ten of them have run by 105ms.
+    tokio::time::sleep(Duration::from_millis(105)).await;
+    assert_eq!(client.ticks().await, 10);
+
+    drop(client);
+    server.await.unwrap();
+}
+
+#[servify::service(tick(poll, every = "20ms"), on_idle(rest, after = "100ms"))]
+struct Poller {
+    pub polls: u32,
+    pub rests: u32,
+}
+
+#[servify::service]
+impl Poller {
+    fn rests(&self) -> u32 {
+        self.rests
+    }
+}
+
+impl Poller::Server {
+    async fn poll(&mut self) {
+        self.polls += 1;
+    }
+
+    async fn rest(&mut self) {
+        self.rests += 1;
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn ticks_do_not_put_off_idle() {
+    let (client, server) = Poller::spawn(
+        Poller::Server { polls: 0, rests: 0 },
+        servify::SpawnConfig::new(),
+    );
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(client.rests().await, 1);
+
+    drop(client);
+    assert!(server.await.unwrap().polls > 0);
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+#[servify::service(
+    impls = [
+        SlowCounter_get,
+        SlowCounter_get_slowly,
+    ]
+)]
+struct SlowCounter {
+    pub counter: u32,
+}
+
+#[servify::export]
+impl SlowCounter {
+    fn get(&self) -> u32 {
+        self.counter
+    }
+
+    fn get_slowly(&self, delay: Duration) -> u32 {
+        tokio::time::sleep(delay).await;
+        self.counter
+    }
+}
+
+#[tokio::test]
+async fn per_client_timeout() {
+    let (counter_rx, counter_client) = SlowCounter::initiate_message_passing(32);
+
+    tokio::spawn(async move {
+        SlowCounter::Server { counter: 3 }.listen(counter_rx).await;
+    });
+
+    let counter_client = counter_client.with_timeout(Duration::from_millis(50));
+
+    assert_eq!(counter_client.try_get().await, Ok(3));
+    assert_eq!(
+        counter_client
+            .try_get_slowly(Duration::from_millis(10))
+            .await,
+        Ok(3)
+    );
+    assert_eq!(
+        counter_client.try_get_slowly(Duration::from_secs(10)).await,
+        Err(servify::Error::Timeout)
+    );
+}
+
+#[tokio::test]
+async fn per_call_timeout() {
+    let (counter_rx, counter_client) = SlowCounter::initiate_message_passing(32);
+
+    tokio::spawn(async move {
+        SlowCounter::Server { counter: 3 }.listen(counter_rx).await;
+    });
+
+    assert_eq!(
+        counter_client.get_slowly(Duration::from_millis(10)).await,
+        3
+    );
+    assert_eq!(
+        counter_client
+            .timeout(Duration::from_millis(50))
+            .try_get_slowly(Duration::from_secs(10))
+            .await,
+        Err(servify::Error::Timeout)
+    );
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Instrument, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+/// A span as seen by [`Recorder`]: its name, the name of its parent and the fields recorded on it.
+#[derive(Debug, Default)]
+struct SpanInfo {
+    name: &'static str,
+    parent: Option<&'static str>,
+    fields: Vec<&'static str>,
+}
+
+#[derive(Default)]
+struct Spans {
+    spans: HashMap<u64, SpanInfo>,
+    metadata: HashMap<u64, &'static Metadata<'static>>,
+    entered: Vec<u64>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Spans>>);
+
+impl Recorder {
+    fn find(&self, name: &str) -> SpanInfo {
+        let spans = self.0.lock().unwrap();
+        let span = spans.spans.values().find(|span| span.name == name).unwrap();
+        SpanInfo {
+            name: span.name,
+            parent: span.parent,
+            fields: span.fields.clone(),
+        }
+    }
+}
+
+struct FieldNames<'a>(&'a mut Vec<&'static str>);
+
+impl Visit for FieldNames<'_> {
+    fn record_debug(&mut self, field: &Field, _: &dyn Debug) {
+        self.0.push(field.name());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let mut spans = self.0.lock().unwrap();
+        let id = spans.spans.len() as u64 + 1;
+        let parent = match attrs.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attrs.is_contextual() => spans.entered.last().copied(),
+            None => None,
+        };
+        let parent = parent.map(|parent| spans.spans[&parent].name);
+        spans.metadata.insert(id, attrs.metadata());
+        spans.spans.insert(
+            id,
+            SpanInfo {
+                name: attrs.metadata().name(),
+                parent,
+                fields: vec![],
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.0.lock().unwrap();
+        let span = spans.spans.get_mut(&span.into_u64()).unwrap();
+        values.record(&mut FieldNames(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        self.0.lock().unwrap().entered.push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.lock().unwrap().entered.pop();
+    }
+
+    fn current_span(&self) -> Current {
+        let spans = self.0.lock().unwrap();
+        match spans.entered.last() {
+            Some(id) => Current::new(Id::from_u64(*id), spans.metadata[id]),
+            None => Current::none(),
+        }
+    }
+}
+
+#[servify::service]
+struct Counter {
+    pub count: u32,
+}
+
+#[servify::service]
+impl Counter {
+    fn increment(&mut self, count: u32) -> u32 {
+        tracing::info_span!("handler").in_scope(|| {
+            self.count += count;
+            self.count
+        })
+    }
+}
+
+#[tokio::test]
+async fn call_spans_follow_the_caller() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let (client, server) =
+        Counter::spawn(Counter::Server { count: 0 }, servify::SpawnConfig::new());
+    let count = client
+        .increment(2)
+        .instrument(tracing::info_span!("caller"))
+        .await;
+    assert_eq!(count, 2);
+
+    drop(client);
+    server.await.unwrap();
+
+    let call = recorder.find("Counter::increment");
+    assert_eq!(call.parent, Some("caller"));
+    assert_eq!(call.fields, vec!["queued", "handled"]);
+    assert_eq!(recorder.find("handler").parent, Some("Counter::increment"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn scheduled_call_spans_follow_the_caller() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let (client, server) =
+        Counter::spawn(Counter::Server { count: 0 }, servify::SpawnConfig::new());
+    let scheduled = tracing::info_span!("scheduler")
+        .in_scope(|| client.after(Duration::from_millis(50)).increment(3));
+    assert_eq!(scheduled.await, Ok(3));
+
+    drop(client);
+    server.await.unwrap();
+
+    assert_eq!(
+        recorder.find("Counter::increment").parent,
+        Some("scheduler")
+    );
+}
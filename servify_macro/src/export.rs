@@ -2,175 +2,670 @@ use case::CaseExt;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse::{ParseStream, Parser},
+    parenthesized,
+    parse::{Parse, ParseStream, Parser},
+    parse2, parse_quote,
     punctuated::Punctuated,
     spanned::Spanned,
-    Error, Expr, ExprField, ExprPath, Field, FieldMutability, FieldValue, FieldsNamed, FnArg,
-    Ident, ImplItem, ImplItemFn, ItemImpl, Member, Pat, PatType, Result, Token, TypePath,
-    Visibility,
+    Attribute, Error, Expr, ExprField, ExprPath, Field, FieldMutability, FieldValue, FieldsNamed,
+    FnArg, GenericArgument, GenericParam, Generics, Ident, ImplItem, ImplItemFn, ItemImpl, LitStr,
+    Member, Pat, PatType, Path, PathArguments, Result, Token, Type, TypePath, TypeReference,
 };
 
-use crate::util::{return_type_ext::ReturnTypeExt, type_path_ext::TypePathExt};
+use crate::util::{
+    duration::DurationLit, generics_ext::GenericsExt, return_type_ext::ReturnTypeExt,
+    type_path_ext::TypePathExt,
+};
+
+pub(crate) fn impl_export(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    export(attrs, item, false)
+}
+
+/// Like [`impl_export`], for impl blocks marked with `#[servify::service]`, whose hooks are handed
+/// over to `Server::listen` along with the exported methods.
+pub(crate) fn impl_export_with_hooks(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    export(attrs, item, true)
+}
+
+fn export(attrs: TokenStream, item: TokenStream, hooks: bool) -> TokenStream {
+    parse2::<ExportAttrs>(attrs)
+        .and_then(|attrs| (|input: ParseStream| parse(input, attrs, hooks)).parse2(item))
+        .unwrap_or_else(Error::into_compile_error)
+}
 
-pub(crate) fn impl_export(_attrs: TokenStream, item: TokenStream) -> TokenStream {
-    parse.parse2(item).unwrap_or_else(Error::into_compile_error)
+/// Options given to `#[servify::export(...)]`.
+struct ExportAttrs {
+    /// Extra derives for the generated request structs, next to `Clone`.
+    derives: Vec<Path>,
+}
+
+impl Parse for ExportAttrs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut derives = vec![];
+
+        while !input.is_empty() {
+            let property_name: Ident = input.parse()?;
+            match property_name.to_string().as_str() {
+                "derive" => {
+                    let group;
+                    let _paren = parenthesized!(group in input);
+                    derives.extend(Punctuated::<Path, Token![,]>::parse_terminated(&group)?);
+                }
+                _ => {
+                    return Err(Error::new(
+                        property_name.span(),
+                        "Unknown property. expected `derive`",
+                    ))
+                }
+            }
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Self { derives })
+    }
 }
 
 struct ExportParent {
     mod_path: TypePath,
+    generics: Generics,
+    derives: Vec<Path>,
 }
 
-fn parse(input: ParseStream) -> Result<TokenStream> {
+fn parse(input: ParseStream, attrs: ExportAttrs, hooks: bool) -> Result<TokenStream> {
     let top: ItemImpl = input.parse()?;
-    let mod_path = match *top.self_ty {
+    let mut mod_path = match *top.self_ty {
         syn::Type::Path(path) => path,
         _ => Err(Error::new(
             top.self_ty.span(),
             "servify_macro::export can only be used on impl blocks with a TypePath.",
         ))?,
     };
+    // The type arguments of `impl<K, V> Cache<K, V>` belong to the generated items, not the module.
+    mod_path.path.segments.last_mut().unwrap().arguments = PathArguments::None;
 
-    let parent = ExportParent { mod_path };
-    top.items
+    if let Some(param) = top
+        .generics
+        .params
         .iter()
-        .map(|item| match item {
-            ImplItem::Fn(item) => parse_method(item, &parent),
+        .find(|param| !matches!(param, GenericParam::Type(_)))
+    {
+        return Err(Error::new(
+            param.span(),
+            "servify_macro::export only supports type parameters.",
+        ));
+    }
+
+    let parent = ExportParent {
+        mod_path,
+        generics: top.generics,
+        derives: attrs.derives,
+    };
+
+    // Skipped methods and consts are kept as plain items of `Server`.
+    let mut server_items: Vec<ImplItem> = vec![];
+    let mut exports = TokenStream::new();
+    for item in &top.items {
+        match item {
+            ImplItem::Fn(item) => {
+                let attrs = MethodAttrs::parse(&item.attrs)?;
+                // Nothing would ever call the hooks of a block listed through `impls = [...]`.
+                if attrs.hook.is_some() && !hooks {
+                    let attr = item.attrs.iter().find(|attr| MethodAttrs::is_servify(attr));
+                    return Err(Error::new(
+                        attr.unwrap().span(),
+                        "servify_macro::export cannot run hooks. Mark the impl block with `#[servify::service]` instead, or name the method in the options of the struct.",
+                    ));
+                }
+                if attrs.skip || attrs.hook.is_some() {
+                    let mut item = item.clone();
+                    item.attrs.retain(|attr| !MethodAttrs::is_servify(attr));
+                    // Hooks are awaited by `Server::listen`, like the handlers of exported methods.
+                    if attrs.hook.is_some() {
+                        item.sig.asyncness.get_or_insert_with(Default::default);
+                    }
+                    server_items.push(ImplItem::Fn(item));
+                } else {
+                    exports.extend(parse_method(item, &parent, attrs)?);
+                }
+            }
+            ImplItem::Const(_) | ImplItem::Macro(_) => server_items.push(item.clone()),
+            // `Server` gets an inherent impl, where associated types are unstable.
+            ImplItem::Type(item) => Err(Error::new(
+                item.span(),
+                "servify_macro::export cannot keep associated types, which inherent impls do not support. Declare the type outside the impl block.",
+            ))?,
             item => Err(Error::new(
                 item.span(),
-                "servify_macro::export cannot handle implementations other than functions.",
-            )),
-        })
-        .collect()
+                "servify_macro::export cannot handle implementations other than functions and constants.",
+            ))?,
+        }
+    }
+
+    if !server_items.is_empty() {
+        let server_path = parent.mod_path.clone().with_trail_ident("Server");
+        let (impl_generics, ty_generics, where_clause) = parent.generics.split_for_impl();
+        exports.extend(quote! {
+            impl #impl_generics #server_path #ty_generics #where_clause {
+                #(#server_items)*
+            }
+        });
+    }
+    Ok(exports)
+}
+
+/// Options given to a single method through `#[servify(...)]`.
+#[derive(Default)]
+pub(crate) struct MethodAttrs {
+    /// Keeps the method as a plain method of `Server` instead of exporting it.
+    pub(crate) skip: bool,
+    /// The name the method is exported under, in place of its own name.
+    pub(crate) rename: Option<Ident>,
+    /// Streams items to the client through the method's `StreamSender` argument.
+    pub(crate) stream: bool,
+    /// Returns to the client once the request is enqueued, without waiting for the server.
+    pub(crate) oneway: bool,
+    /// Runs the method at a point of the server's lifecycle, or periodically, instead of exporting it.
+    pub(crate) hook: Option<Hook>,
+}
+
+pub(crate) enum Hook {
+    Start,
+    Stop,
+    Idle(DurationLit),
+    Every(DurationLit),
+}
+
+impl MethodAttrs {
+    pub(crate) fn is_servify(attr: &Attribute) -> bool {
+        attr.path().is_ident("servify")
+    }
+
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| Self::is_servify(attr)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    parsed.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    parsed.rename = Some(name.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("stream") {
+                    parsed.stream = true;
+                    Ok(())
+                } else if meta.path.is_ident("oneway") {
+                    parsed.oneway = true;
+                    Ok(())
+                } else if meta.path.is_ident("on_start") {
+                    parsed.hook = Some(Hook::Start);
+                    Ok(())
+                } else if meta.path.is_ident("on_stop") {
+                    parsed.hook = Some(Hook::Stop);
+                    Ok(())
+                } else if meta.path.is_ident("every") {
+                    parsed.hook = Some(Hook::Every(meta.value()?.parse()?));
+                    Ok(())
+                } else if meta.path.is_ident("on_idle") {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("after") {
+                            parsed.hook = Some(Hook::Idle(meta.value()?.parse()?));
+                            Ok(())
+                        } else {
+                            Err(meta.error("Unknown property. expected `after`"))
+                        }
+                    })
+                } else {
+                    Err(meta.error(
+                        "Unknown property. expected `skip`, `rename`, `stream`, `oneway`, `on_start`, `on_stop`, `on_idle` or `every`",
+                    ))
+                }
+            })?;
+        }
+        Ok(parsed)
+    }
 }
 
-fn parse_method(input: &ImplItemFn, parent: &ExportParent) -> Result<TokenStream> {
+/// Methods every generated client has, which exported methods would collide with.
+const RESERVED_CLIENT_METHODS: &[&str] = &[
+    "abandoned_requests",
+    "after",
+    "at",
+    "subscribe",
+    "timeout",
+    "with_timeout",
+];
+
+fn parse_method(
+    input: &ImplItemFn,
+    parent: &ExportParent,
+    method_attrs: MethodAttrs,
+) -> Result<TokenStream> {
     let mod_path = parent.mod_path.clone();
 
     let struct_name = mod_path.path.segments.last().unwrap().ident.clone();
 
-    let fn_name = input.sig.ident.clone();
+    let fn_name = method_attrs
+        .rename
+        .unwrap_or_else(|| input.sig.ident.clone());
+
+    if RESERVED_CLIENT_METHODS.contains(&fn_name.to_string().as_str()) {
+        return Err(Error::new(
+            fn_name.span(),
+            format!(
+                "servify_macro::export cannot export `{}`, the generated client already has a method with that name. Use `#[servify(rename = ...)]` to export it under another name.",
+                fn_name
+            ),
+        ));
+    }
+
+    if !input.sig.generics.params.is_empty() {
+        return Err(Error::new(
+            input.sig.generics.span(),
+            "servify_macro::export cannot export generic methods. Declare the parameters on the service instead.",
+        ));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = parent.generics.split_for_impl();
+    let derives = &parent.derives;
+    let turbofish = ty_generics.as_turbofish();
+    let type_params = parent
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+    let phantom = (!type_params.is_empty())
+        .then(|| quote! { ::std::marker::PhantomData<fn() -> (#(#type_params,)*)> });
 
     let export_name = Ident::new(&format!("{}_{}", struct_name, fn_name), Span::call_site());
 
+    // Prefixed with the struct name so services sharing a module don't collide.
     let request_name = Ident::new(
-        &format!("__{}_request", fn_name.to_string().to_snake()),
+        &format!(
+            "__{}_{}_request",
+            struct_name,
+            fn_name.to_string().to_snake()
+        ),
         Span::call_site(),
     );
 
     let response_name = Ident::new(
-        &format!("__{}_response", fn_name.to_string().to_snake()),
+        &format!(
+            "__{}_{}_response",
+            struct_name,
+            fn_name.to_string().to_snake()
+        ),
         Span::call_site(),
     );
 
     let server_path = mod_path.clone().with_trail_ident("Server");
     let client_path = mod_path.clone().with_trail_ident("Client");
 
-    let internal_fn_name = Ident::new(&format!("__internal_{}", fn_name), Span::call_site());
+    let internal_fn_name = Ident::new(&format!("__internal_{}", fn_name), fn_name.span());
+    let internal_try_fn_name = Ident::new(&format!("__internal_try_{}", fn_name), fn_name.span());
+    let try_fn_name = Ident::new(&format!("try_{}", fn_name), Span::call_site());
+    let variant_name = Ident::new(&fn_name.to_string().to_camel(), fn_name.span());
+    let locked_fn_name = Ident::new(&format!("__locked_{}", fn_name), Span::call_site());
+
+    let mutability = match input.sig.receiver() {
+        Some(receiver) if receiver.reference.is_some() => receiver.mutability,
+        receiver => Err(Error::new(
+            receiver.map_or(input.sig.span(), |r| r.span()),
+            "servify_macro::export can only export methods taking `&self` or `&mut self`.",
+        ))?,
+    };
+    let lock = match mutability {
+        Some(_) => quote! { write_owned },
+        None => quote! { read_owned },
+    };
 
     let sig = input.sig.inputs.clone();
 
-    let sig_without_self = sig
-        .clone()
-        .into_iter()
-        .filter(|i| match i {
-            FnArg::Typed(_) => true,
-            FnArg::Receiver(_) => false,
-        })
-        .collect::<Punctuated<FnArg, Token![,]>>();
-
     let body = input.block.clone();
+    // Docs describe the call for users of the client, other attributes stay on the handler.
+    let (docs, attrs): (Vec<_>, Vec<_>) = input
+        .attrs
+        .iter()
+        .filter(|attr| !MethodAttrs::is_servify(attr))
+        .cloned()
+        .partition(|attr| attr.path().is_ident("doc"));
+    let try_doc = format!(
+        "Like [`{0}`](Self::{0}), but returns an error instead of panicking when the call fails.",
+        fn_name
+    );
     let response = input.sig.output.clone().to_type();
+    let returns_unit = matches!(&response, Type::Tuple(tuple) if tuple.elems.is_empty());
+    // A stream only reports whether its handler succeeded, so there is nowhere to return a value.
+    if method_attrs.stream && !returns_unit {
+        return Err(Error::new(
+            input.sig.output.span(),
+            "servify_macro::export cannot export `#[servify(stream)]` methods returning a value.",
+        ));
+    }
+    // Oneway requests have nobody waiting for the response, so there must not be one.
+    let responder = if method_attrs.oneway {
+        if !returns_unit {
+            return Err(Error::new(
+                input.sig.output.span(),
+                "servify_macro::export cannot export `#[servify(oneway)]` methods returning a value.",
+            ));
+        }
+        quote! { () }
+    } else {
+        quote! { ::tokio::sync::oneshot::Sender<::std::result::Result<#response, ::servify::Error>> }
+    };
 
-    let request_sig = sig_without_self
-        .clone()
-        .into_iter()
+    let mut request_sig = sig
+        .iter()
         .filter_map(|i| match i {
-            FnArg::Typed(PatType { pat, ty, .. }) => match *pat {
-                Pat::Ident(ident) => {
-                    Some((Ident::new(&ident.ident.to_string(), Span::call_site()), *ty))
-                }
+            FnArg::Typed(PatType { pat, ty, .. }) => Some((pat, ty)),
+            FnArg::Receiver(_) => None,
+        })
+        .enumerate()
+        .map(|(index, (pat, ty))| {
+            let ident = match &**pat {
+                Pat::Ident(ident) => ident.ident.to_string(),
+                _ => format!("__arg{}", index),
+            };
+            // Borrowed arguments are sent as their owned form and lent back to the handler.
+            let owned = match &**ty {
+                Type::Reference(TypeReference {
+                    mutability: Some(mutability),
+                    ..
+                }) => Err(Error::new(
+                    mutability.span(),
+                    "servify_macro::export cannot export methods taking `&mut` arguments.",
+                ))?,
+                Type::Reference(TypeReference { elem, .. }) => Some(parse_quote! {
+                    <#elem as ::std::borrow::ToOwned>::Owned
+                }),
                 _ => None,
-            },
-            _ => None,
+            };
+            Ok((Ident::new(&ident, Span::call_site()), (**ty).clone(), owned))
         })
+        .collect::<Result<Vec<(Ident, Type, Option<Type>)>>>()?;
+
+    // A `servify::Context` argument is handed over by the server instead of being sent by the
+    // client. It is matched by its full path, so a type of the user's named `Context` stays an
+    // ordinary argument.
+    let context = request_sig
+        .iter()
+        .position(|(_, ty, _)| is_servify_type(ty, "Context"))
+        .map(|position| (position, request_sig.remove(position).1));
+
+    // The item type and argument name of the `StreamSender` a streaming method feeds.
+    let stream = if method_attrs.stream {
+        let sender = request_sig.iter().find_map(|(ident, ty, _)| {
+            type_argument(ty, "StreamSender").map(|item| (ident.clone(), item.clone()))
+        });
+        Some(sender.ok_or_else(|| {
+            Error::new(
+                input.sig.span(),
+                "servify_macro::export needs a `servify::StreamSender<T>` argument on `#[servify(stream)]` methods.",
+            )
+        })?)
+    } else {
+        None
+    };
+
+    let request_new_sig = request_sig
+        .iter()
+        .map(|(ident, ty, _)| quote! { #ident: #ty })
+        .collect::<Punctuated<TokenStream, Token![,]>>();
+
+    // The client of a streaming method creates the sender itself.
+    let client_args = request_sig
+        .iter()
+        .filter(|(ident, _, _)| stream.as_ref().is_none_or(|(sender, _)| ident != sender))
         .collect::<Vec<_>>();
+    let client_sig = client_args
+        .iter()
+        .map(|(ident, ty, _)| quote! { #ident: #ty })
+        .collect::<Punctuated<TokenStream, Token![,]>>();
+    let client_arg_names = client_args.iter().map(|(ident, _, _)| ident);
 
-    let struct_block = FieldsNamed {
+    let mut struct_block = FieldsNamed {
         brace_token: Default::default(),
         named: request_sig
             .clone()
             .into_iter()
-            .map(|(ident, ty)| Field {
+            .map(|(ident, ty, owned)| Field {
                 attrs: Default::default(),
-                vis: Visibility::Inherited,
+                vis: parse_quote! { pub },
                 ident: Some(ident),
                 colon_token: Default::default(),
-                ty,
+                ty: owned.unwrap_or(ty),
                 mutability: FieldMutability::None,
             })
             .collect(),
     };
+    if let Some(phantom) = &phantom {
+        struct_block
+            .named
+            .push(parse_quote! { __phantom: #phantom });
+    }
 
-    let call_server_args: Punctuated<ExprField, Token![,]> = request_sig
+    let mut call_server_args: Punctuated<Expr, Token![,]> = request_sig
         .clone()
         .into_iter()
-        .map(|(ident, _)| ExprField {
-            attrs: Default::default(),
-            member: Member::Named(Ident::new(&ident.to_string(), Span::call_site())),
-            dot_token: Default::default(),
-            base: Box::new(Expr::Path(ExprPath {
+        .map(|(ident, _, owned)| {
+            let field = Expr::Field(ExprField {
                 attrs: Default::default(),
-                qself: None,
-                path: Ident::new("req", Span::call_site()).into(),
-            })),
+                member: Member::Named(Ident::new(&ident.to_string(), Span::call_site())),
+                dot_token: Default::default(),
+                base: Box::new(Expr::Path(ExprPath {
+                    attrs: Default::default(),
+                    qself: None,
+                    path: Ident::new("req", Span::call_site()).into(),
+                })),
+            });
+            match owned {
+                Some(_) => parse_quote! { ::std::borrow::Borrow::borrow(&#field) },
+                None => field,
+            }
         })
         .collect();
 
-    let call_client_args: Punctuated<FieldValue, Token![,]> = request_sig
+    if let Some((position, _)) = &context {
+        call_server_args.insert(*position, parse_quote! { ctx });
+    }
+
+    let mut call_client_args: Punctuated<FieldValue, Token![,]> = request_sig
         .clone()
         .into_iter()
-        .map(|(ident, _)| FieldValue {
-            attrs: Default::default(),
-            member: Member::Named(Ident::new(&ident.to_string(), Span::call_site())),
-            colon_token: Default::default(),
-            expr: Expr::Path(ExprPath {
+        .map(|(ident, _, owned)| match owned {
+            Some(_) => parse_quote! { #ident: ::std::borrow::ToOwned::to_owned(#ident) },
+            None => FieldValue {
                 attrs: Default::default(),
-                qself: None,
-                path: Ident::new(&ident.to_string(), Span::call_site()).into(),
-            }),
+                member: Member::Named(Ident::new(&ident.to_string(), Span::call_site())),
+                colon_token: Default::default(),
+                expr: Expr::Path(ExprPath {
+                    attrs: Default::default(),
+                    qself: None,
+                    path: Ident::new(&ident.to_string(), Span::call_site()).into(),
+                }),
+            },
         })
         .collect();
+    if phantom.is_some() {
+        call_client_args.push(parse_quote! { __phantom: ::std::marker::PhantomData });
+    }
+
+    let dispatch_fn_name = Ident::new(&format!("__dispatch_{}", fn_name), Span::call_site());
+    let (server_fn, dispatch_fn, locked_fn) = match &context {
+        Some((_, context)) => (
+            quote! {
+                pub async fn #fn_name(&#mutability self, req: #request_name #ty_generics, ctx: #context) -> #response_name #ty_generics {
+                    self.#internal_fn_name(#call_server_args).await
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                pub async fn #dispatch_fn_name(&#mutability self, req: #request_name #ty_generics, ctx: &#context) -> #response_name #ty_generics {
+                    self.#fn_name(req, ::std::clone::Clone::clone(ctx)).await
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                pub async fn #locked_fn_name(
+                    state: ::std::sync::Arc<::tokio::sync::RwLock<Self>>,
+                    req: #request_name #ty_generics,
+                    ctx: #context
+                ) -> impl ::std::future::Future<Output = #response_name #ty_generics> {
+                    let #mutability guard = state.#lock().await;
+                    async move { guard.#dispatch_fn_name(req, &ctx).await }
+                }
+            },
+        ),
+        None => (
+            quote! {
+                pub async fn #fn_name(&#mutability self, req: #request_name #ty_generics) -> #response_name #ty_generics {
+                    self.#internal_fn_name(#call_server_args).await
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                pub async fn #dispatch_fn_name<__Event>(&#mutability self, req: #request_name #ty_generics, _: &::servify::Context<__Event>) -> #response_name #ty_generics {
+                    self.#fn_name(req).await
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                pub async fn #locked_fn_name<__Event>(
+                    state: ::std::sync::Arc<::tokio::sync::RwLock<Self>>,
+                    req: #request_name #ty_generics,
+                    ctx: ::servify::Context<__Event>
+                ) -> impl ::std::future::Future<Output = #response_name #ty_generics> {
+                    let #mutability guard = state.#lock().await;
+                    async move { guard.#dispatch_fn_name(req, &ctx).await }
+                }
+            },
+        ),
+    };
+
+    // Scheduled calls run in a task of their own.
+    let scheduler_path = mod_path.clone().with_trail_ident("Scheduler");
+    let task_generics = parent.generics.with_task_bounds();
+    let task_where_clause = task_generics.split_for_impl().2;
+    let schedule_doc = format!(
+        "Schedules a call to `{}`, delivered when the scheduler's time is reached.",
+        fn_name
+    );
+
+    let client_impl = match &stream {
+        Some((sender, item)) => {
+            let internal_send_fn_name =
+                Ident::new(&format!("__internal_send_{}", fn_name), fn_name.span());
+            quote! {
+                impl #impl_generics #client_path #ty_generics #where_clause {
+                    #(#docs)*
+                    pub async fn #fn_name(&self, #client_sig) -> ::servify::ResponseStream<#item> {
+                        self.#try_fn_name(#(#client_arg_names),*).await.unwrap()
+                    }
+                    #[doc = #try_doc]
+                    pub async fn #try_fn_name(&self, #client_sig) -> ::std::result::Result<::servify::ResponseStream<#item>, ::servify::Error> {
+                        let (#sender, __stream) = ::servify::__private::stream_channel();
+                        let reply = #mod_path::#internal_send_fn_name(self, #request_name { #call_client_args }).await?;
+                        ::std::result::Result::Ok(::servify::__private::keep_alive(__stream, reply))
+                    }
+                }
+            }
+        }
+        None => quote! {
+            impl #impl_generics #client_path #ty_generics #where_clause {
+                #(#docs)*
+                pub async fn #fn_name(&self, #client_sig) -> #response_name #ty_generics {
+                    #mod_path::#internal_fn_name(self, #request_name { #call_client_args }).await
+                }
+                #[doc = #try_doc]
+                pub async fn #try_fn_name(&self, #client_sig) -> ::std::result::Result<#response_name #ty_generics, ::servify::Error> {
+                    #mod_path::#internal_try_fn_name(self, #request_name { #call_client_args }).await
+                }
+            }
+
+            impl #impl_generics #scheduler_path #ty_generics #task_where_clause {
+                #[doc = #schedule_doc]
+                pub fn #fn_name(&self, #client_sig) -> ::servify::Scheduled<#response_name #ty_generics> {
+                    let req = #request_name { #call_client_args };
+                    self.__schedule(move |client| async move { #mod_path::#internal_try_fn_name(&client, req).await })
+                }
+            }
+        },
+    };
 
     Ok(quote! {
+        #[doc(hidden)]
         #[allow(non_camel_case_types)]
-        pub type #response_name = #response;
+        pub type #response_name #ty_generics = <#export_name #ty_generics as ::servify::ServifyExport>::Response;
 
+        #[doc(hidden)]
         #[allow(non_camel_case_types)]
-        #[derive(Clone)]
-        pub struct #request_name #struct_block
+        #[derive(Clone #(, #derives)*)]
+        pub struct #request_name #impl_generics #where_clause #struct_block
 
-        impl #server_path {
-            pub async fn #fn_name(&mut self, req: #request_name) -> #response_name {
-                self.#internal_fn_name(#call_server_args).await
+        impl #impl_generics #request_name #ty_generics #where_clause {
+            #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+            pub fn new(#request_new_sig) -> Self {
+                Self { #call_client_args }
             }
-            async fn #internal_fn_name(#sig) -> #response_name #body
         }
 
-        impl #client_path {
-            pub async fn #fn_name(&self, #sig_without_self) -> #response_name {
-                #mod_path::#internal_fn_name(self, #request_name { #call_client_args }).await
-            }
+        impl #impl_generics #server_path #ty_generics #where_clause {
+            #server_fn
+            #dispatch_fn
+            #locked_fn
+            #(#attrs)*
+            async fn #internal_fn_name(#sig) -> #response_name #ty_generics #body
         }
 
+        #client_impl
+
+        // Fails to compile when the method is missing from the `impls` list of the service.
+        const _: () = {
+            #[allow(dead_code)]
+            fn check #impl_generics() #where_clause {
+                let _ = #mod_path::Message #turbofish::#variant_name;
+            }
+        };
+
         #[allow(non_camel_case_types)]
-        pub struct #export_name ();
-        impl ::servify::ServifyExport for #export_name {
-            type Request = #request_name;
-            type Response = #response_name;
+        pub struct #export_name #ty_generics (#phantom);
+        impl #impl_generics ::servify::ServifyExport for #export_name #ty_generics #where_clause {
+            type Request = #request_name #ty_generics;
+            type Response = #response;
+            type Responder = #responder;
         }
     })
 }
 
+/// Returns `T` if `ty` is a `<name><T>`, such as `StreamSender<T>`.
+/// Whether `ty` is written as `servify::<name><..>` or `::servify::<name><..>`.
+fn is_servify_type(ty: &Type, name: &str) -> bool {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    let segments = path.segments.iter().collect::<Vec<_>>();
+    matches!(&segments[..], [krate, item] if krate.ident == "servify" && item.ident == name)
+}
+
+fn type_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(item) => Some(item),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::impl_export;
@@ -178,15 +673,15 @@ mod tests {
     use quote::quote;
 
     #[test]
-    fn fail_if_contains_const() {
+    fn fail_if_unknown_method_option() {
         assert_eq! {
             impl_export(quote!{}, quote!{
                 impl A {
-                    const A: usize = 0;
+                    #[servify(hide)]
                     fn a(&self) {}
                 }
             }).to_string(),
-            r#":: core :: compile_error ! { "servify_macro::export cannot handle implementations other than functions." }"#,
+            r#":: core :: compile_error ! { "Unknown property. expected `skip`, `rename`, `stream`, `oneway`, `on_start`, `on_stop`, `on_idle` or `every`" }"#,
         };
     }
 
@@ -201,10 +696,113 @@ mod tests {
     }
 
     #[test]
-    fn test_export() {
+    fn fail_if_associated_type() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl SomeStruct {
+                    const MAX: u32 = 10;
+                    type Item = u32;
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot keep associated types, which inherent impls do not support. Declare the type outside the impl block." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_hook_under_export() {
         assert_eq! {
             impl_export(quote!{}, quote!{
                 impl SomeStruct {
+                    #[servify(on_start)]
+                    fn start(&mut self) {}
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot run hooks. Mark the impl block with `#[servify::service]` instead, or name the method in the options of the struct." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_generic_method() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl A {
+                    fn a<T>(&self, value: T) {}
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot export generic methods. Declare the parameters on the service instead." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_reserved_client_method() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl A {
+                    fn at(&self, index: usize) -> u32 { 0 }
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot export `at`, the generated client already has a method with that name. Use `#[servify(rename = ...)]` to export it under another name." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_mut_reference_argument() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl A {
+                    fn a(&self, values: &mut Vec<u32>) {}
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot export methods taking `&mut` arguments." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_stream_without_sender() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl A {
+                    #[servify(stream)]
+                    fn a(&self, from: usize) {}
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export needs a `servify::StreamSender<T>` argument on `#[servify(stream)]` methods." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_stream_returns_value() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl SomeStruct {
+                    #[servify(stream)]
+                    fn a(&self, sender: servify::StreamSender<u32>) -> u32 { 0 }
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot export `#[servify(stream)]` methods returning a value." }"#
+        };
+    }
+
+    #[test]
+    fn fail_if_oneway_returns_value() {
+        assert_eq! {
+            impl_export(quote!{}, quote!{
+                impl A {
+                    #[servify(oneway)]
+                    fn a(&self) -> u32 { 0 }
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "servify_macro::export cannot export `#[servify(oneway)]` methods returning a value." }"#
+        };
+    }
+
+    #[test]
+    fn test_export() {
+        assert_eq! {
+            impl_export(quote!{ derive(Debug, PartialEq) }, quote!{
+                impl SomeStruct {
+                    /// Adds `count` to the counter.
+                    #[allow(clippy::needless_return)]
                     fn increment(&mut self, count: u32) -> u32 {
                         self.count += count;
                         self.count
@@ -213,36 +811,82 @@ mod tests {
             }).to_string(),
 
             quote!{
+                #[doc(hidden)]
                 #[allow(non_camel_case_types)]
-                pub type __increment_response = u32;
-
+                pub type __SomeStruct_increment_response = <SomeStruct_increment as ::servify::ServifyExport>::Response;
+                #[doc(hidden)]
                 #[allow(non_camel_case_types)]
-                #[derive(Clone)]
-                pub struct __increment_request {
-                    count: u32
+                #[derive(Clone, Debug, PartialEq)]
+                pub struct __SomeStruct_increment_request {
+                    pub count: u32
+                }
+
+                impl __SomeStruct_increment_request {
+                    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+                    pub fn new(count: u32) -> Self {
+                        Self { count }
+                    }
                 }
 
                 impl SomeStruct::Server {
-                    pub async fn increment(&mut self, req: __increment_request) -> __increment_response {
+                    pub async fn increment(&mut self, req: __SomeStruct_increment_request) -> __SomeStruct_increment_response {
                         self.__internal_increment(req.count).await
                     }
-                    async fn __internal_increment(&mut self, count: u32) -> __increment_response {
+
+                    #[doc(hidden)]
+                    pub async fn __dispatch_increment<__Event>(&mut self, req: __SomeStruct_increment_request, _: &::servify::Context<__Event>) -> __SomeStruct_increment_response {
+                        self.increment(req).await
+                    }
+
+                    #[doc(hidden)]
+                    pub async fn __locked_increment<__Event>(
+                        state: ::std::sync::Arc<::tokio::sync::RwLock<Self>>,
+                        req: __SomeStruct_increment_request,
+                        ctx: ::servify::Context<__Event>
+                    ) -> impl ::std::future::Future<Output = __SomeStruct_increment_response> {
+                        let mut guard = state.write_owned().await;
+                        async move { guard.__dispatch_increment(req, &ctx).await }
+                    }
+
+                    #[allow(clippy::needless_return)]
+                    async fn __internal_increment(&mut self, count: u32) -> __SomeStruct_increment_response {
                         self.count += count;
                         self.count
                     }
                 }
 
                 impl SomeStruct::Client {
-                    pub async fn increment(&self, count: u32) -> __increment_response {
-                        SomeStruct::__internal_increment(self, __increment_request { count }).await
+                    /// Adds `count` to the counter.
+                    pub async fn increment(&self, count: u32) -> __SomeStruct_increment_response {
+                        SomeStruct::__internal_increment(self, __SomeStruct_increment_request { count }).await
+                    }
+
+                    #[doc = "Like [`increment`](Self::increment), but returns an error instead of panicking when the call fails."]
+                    pub async fn try_increment(&self, count: u32) -> ::std::result::Result<__SomeStruct_increment_response, ::servify::Error> {
+                        SomeStruct::__internal_try_increment(self, __SomeStruct_increment_request { count }).await
+                    }
+                }
+
+                impl SomeStruct::Scheduler {
+                    #[doc = "Schedules a call to `increment`, delivered when the scheduler's time is reached."]
+                    pub fn increment(&self, count: u32) -> ::servify::Scheduled<__SomeStruct_increment_response> {
+                        let req = __SomeStruct_increment_request { count };
+                        self.__schedule(move |client| async move { SomeStruct::__internal_try_increment(&client, req).await })
                     }
                 }
 
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn check() {
+                        let _ = SomeStruct::Message::Increment;
+                    }
+                };
                 #[allow(non_camel_case_types)]
-                pub struct SomeStruct_increment ();
+                pub struct SomeStruct_increment();
                 impl ::servify::ServifyExport for SomeStruct_increment {
-                    type Request = __increment_request;
-                    type Response = __increment_response;
+                    type Request = __SomeStruct_increment_request;
+                    type Response = u32;
+                    type Responder = ::tokio::sync::oneshot::Sender<::std::result::Result<u32, ::servify::Error>>;
                 }
             }.to_string()
         };
@@ -1,36 +1,174 @@
 use std::vec;
 
 use case::CaseExt;
+use proc_macro2::Span;
 use proc_macro2::TokenStream;
+use quote::format_ident;
 use quote::quote;
+use quote::ToTokens;
 use syn::bracketed;
+use syn::ext::IdentExt;
+use syn::parenthesized;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
+use syn::parse::Parser;
 use syn::parse2;
+use syn::parse_quote;
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::token::Comma;
+use syn::token::Paren;
 use syn::Error;
+use syn::GenericParam;
 use syn::Ident;
+use syn::ImplItem;
+use syn::ItemImpl;
 use syn::ItemStruct;
+use syn::LitStr;
+use syn::Meta;
+use syn::Path;
 use syn::Result;
 use syn::Token;
+use syn::Type;
 use syn::TypePath;
 
+use crate::export::impl_export_with_hooks;
+use crate::export::Hook;
+use crate::export::MethodAttrs;
+use crate::util::duration::DurationLit;
+use crate::util::generics_ext::GenericsExt;
 use crate::util::type_path_ext::TypePathExt;
 
 pub(crate) fn impl_service(attrs: TokenStream, item: TokenStream) -> TokenStream {
-    parse2::<ServiceParentAttrs>(attrs)
-        .and_then(|attrs| attrs.parse_item(item))
+    if let Ok(item) = parse2::<ItemImpl>(item.clone()) {
+        return impl_service_for_impl(attrs, item).unwrap_or_else(Error::into_compile_error);
+    }
+    parse2::<ServiceParentAttrs>(attrs.clone())
+        .and_then(|parsed| parsed.parse_item(attrs, item))
         .unwrap_or_else(Error::into_compile_error)
 }
 
+/// Name of the macro through which an impl block marked with `#[servify::service]` hands its
+/// methods over to the struct marked with `#[servify::service]`.
+fn registry_macro_name(struct_name: &Ident) -> Ident {
+    format_ident!("__servify_{}", struct_name)
+}
+
+fn impl_service_for_impl(attrs: TokenStream, item: ItemImpl) -> Result<TokenStream> {
+    // Only the options of `servify_macro::export` make sense here, the rest configure the struct.
+    let options = Punctuated::<Meta, Comma>::parse_terminated.parse2(attrs.clone())?;
+    if let Some(option) = options
+        .iter()
+        .find(|option| !option.path().is_ident("derive"))
+    {
+        return Err(Error::new(
+            option.span(),
+            "Options of servify_macro::service other than `derive` belong on the struct, not on the impl block.",
+        ));
+    }
+    let struct_name = match &*item.self_ty {
+        Type::Path(path) => path.path.segments.last().unwrap().ident.clone(),
+        _ => Err(Error::new(
+            item.self_ty.span(),
+            "servify_macro::service can only be used on impl blocks with a TypePath.",
+        ))?,
+    };
+    let mut exports = vec![];
+    let mut hooks = vec![];
+    for item in &item.items {
+        if let ImplItem::Fn(item) = item {
+            let attrs = MethodAttrs::parse(&item.attrs)?;
+            let method = &item.sig.ident;
+            if let Some(hook) = &attrs.hook {
+                hooks.push(match hook {
+                    Hook::Start => quote! { on_start = #method },
+                    Hook::Stop => quote! { on_stop = #method },
+                    Hook::Idle(after) => {
+                        let after = &after.lit;
+                        quote! { on_idle(#method, after = #after) }
+                    }
+                    Hook::Every(every) => {
+                        let every = &every.lit;
+                        quote! { tick(#method, every = #every) }
+                    }
+                });
+            } else if !attrs.skip {
+                let fn_name = attrs.rename.unwrap_or_else(|| item.sig.ident.clone());
+                exports.push(Ident::new(
+                    &format!("{}_{}", struct_name, fn_name),
+                    fn_name.span(),
+                ));
+            }
+        }
+    }
+    let macro_name = registry_macro_name(&struct_name);
+    let export = impl_export_with_hooks(attrs, item.to_token_stream());
+
+    Ok(quote! {
+        #export
+
+        #[doc(hidden)]
+        macro_rules! #macro_name {
+            (($($attrs:tt)*) $($item:tt)*) => {
+                #[::servify::service(impls = [#(#exports),*], #(#hooks,)* $($attrs)*)]
+                $($item)*
+            };
+        }
+
+        #[doc(hidden)]
+        pub(crate) use #macro_name;
+    })
+}
+
 pub struct ServiceParentAttrs {
-    impls: Vec<TypePath>,
+    impls: Option<Vec<TypePath>>,
+    skip_abandoned: bool,
+    catch_panics: Option<PanicPolicy>,
+    concurrent_reads: bool,
+    events: Option<Type>,
+    on_start: Option<Ident>,
+    on_stop: Option<Ident>,
+    on_idle: Option<(Ident, DurationLit)>,
+    ticks: Vec<(Ident, DurationLit)>,
+    middleware: Option<TypePath>,
+}
+
+enum PanicPolicy {
+    Continue,
+    Stop,
+    Restart(Path),
+}
+
+impl Parse for PanicPolicy {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let policy = Ident::parse_any(input)?;
+        match policy.to_string().as_str() {
+            "continue" => Ok(PanicPolicy::Continue),
+            "stop" => Ok(PanicPolicy::Stop),
+            "restart" => {
+                let _eq: Token![=] = input.parse()?;
+                Ok(PanicPolicy::Restart(input.parse()?))
+            }
+            _ => Err(Error::new(
+                policy.span(),
+                "Unknown panic policy. expected `continue`, `stop` or `restart = <factory>`",
+            )),
+        }
+    }
 }
 
 impl Parse for ServiceParentAttrs {
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut impls = vec![];
+        let mut impls = None;
+        let mut skip_abandoned = false;
+        let mut catch_panics = None;
+        let mut concurrent_reads = None;
+        let mut events = None;
+        let mut on_start = None;
+        let mut on_stop = None;
+        let mut on_idle = None;
+        let mut ticks = vec![];
+        let mut middleware = None;
 
         while !input.is_empty() {
             let property_name: Ident = input.parse()?;
@@ -40,12 +178,59 @@ impl Parse for ServiceParentAttrs {
                     let group;
                     let _paren = bracketed!(group in input);
                     let paths = Punctuated::<TypePath, Comma>::parse_terminated(&group)?;
-                    impls.extend(paths);
+                    impls.get_or_insert_with(Vec::new).extend(paths);
+                }
+                "skip_abandoned" => {
+                    skip_abandoned = true;
+                }
+                "concurrent_reads" => {
+                    concurrent_reads = Some(property_name.span());
+                }
+                "events" => {
+                    let _eq: Token![=] = input.parse()?;
+                    events = Some(input.parse()?);
+                }
+                "on_start" | "on_stop" => {
+                    let _eq: Token![=] = input.parse()?;
+                    let hook = match property_name == "on_start" {
+                        true => &mut on_start,
+                        false => &mut on_stop,
+                    };
+                    if hook.replace(input.parse()?).is_some() {
+                        return Err(hook_set_twice(&property_name));
+                    }
+                }
+                "on_idle" => {
+                    if on_idle.replace(parse_timed_hook(input, "after")?).is_some() {
+                        return Err(hook_set_twice(&property_name));
+                    }
+                }
+                "tick" => {
+                    ticks.push(parse_timed_hook(input, "every")?);
+                }
+                "middleware" => {
+                    let _eq: Token![=] = input.parse()?;
+                    middleware = Some(input.parse()?);
+                }
+                "catch_panics" => {
+                    catch_panics = Some(if input.peek(Paren) {
+                        let group;
+                        let _paren = parenthesized!(group in input);
+                        group.parse()?
+                    } else {
+                        PanicPolicy::Continue
+                    });
+                }
+                "derive" => {
+                    return Err(Error::new(
+                        property_name.span(),
+                        "Request derives are chosen per impl block. Put `derive(...)` on `#[servify::export]` or on the impl block marked with `#[servify::service]`.",
+                    ))
                 }
                 _ => {
                     return Err(Error::new(
                         property_name.span(),
-                        "Unknown property. expected `impls`",
+                        "Unknown property. expected `impls`, `skip_abandoned`, `catch_panics`, `concurrent_reads`, `events`, `on_start`, `on_stop`, `on_idle`, `tick` or `middleware`",
                     ))
                 }
             }
@@ -53,76 +238,368 @@ impl Parse for ServiceParentAttrs {
                 input.parse::<Token![,]>()?;
             }
         }
-        Ok(Self { impls })
+        if let (Some(span), Some(PanicPolicy::Stop | PanicPolicy::Restart(_))) =
+            (concurrent_reads, &catch_panics)
+        {
+            return Err(Error::new(
+                span,
+                "`concurrent_reads` can only be combined with `catch_panics(continue)`",
+            ));
+        }
+        Ok(Self {
+            impls,
+            skip_abandoned,
+            catch_panics,
+            concurrent_reads: concurrent_reads.is_some(),
+            events,
+            on_start,
+            on_stop,
+            on_idle,
+            ticks,
+            middleware,
+        })
+    }
+}
+
+/// Parses `(method, <key> = "duration")`.
+fn parse_timed_hook(input: ParseStream, key: &str) -> Result<(Ident, DurationLit)> {
+    let group;
+    let _paren = parenthesized!(group in input);
+    let method: Ident = group.parse()?;
+    let _comma: Token![,] = group.parse()?;
+    let property_name: Ident = group.parse()?;
+    if property_name != key {
+        return Err(Error::new(
+            property_name.span(),
+            format!("expected `{}`", key),
+        ));
     }
+    let _eq: Token![=] = group.parse()?;
+    Ok((method, group.parse()?))
+}
+
+fn hook_set_twice(property_name: &Ident) -> Error {
+    Error::new(
+        property_name.span(),
+        format!("`{}` is set more than once", property_name),
+    )
+}
+
+#[derive(Default)]
+struct EventTokens {
+    receiver_field: TokenStream,
+    client_field: TokenStream,
+    client_clone: TokenStream,
+    subscribe: TokenStream,
+    init: TokenStream,
+    receiver_init: TokenStream,
+    client_init: TokenStream,
 }
 
 struct ImplTokens {
     internal_function: TokenStream,
     enum_element: TokenStream,
     server_arm: TokenStream,
+    reject_arm: TokenStream,
 }
 
 impl ServiceParentAttrs {
-    fn parse_item(self, item: TokenStream) -> Result<TokenStream> {
+    /// Resolves the method name behind every `impls` entry, rejecting entries that are not
+    /// named `<Struct>_<method>` and entries that are listed more than once.
+    fn fn_names(impls: &[TypePath], mod_name: &Ident) -> Result<Vec<Ident>> {
+        let prefix = format!("{}_", mod_name);
+        let mut fn_names: Vec<Ident> = vec![];
+        let mut errors: Option<Error> = None;
+        for path in impls {
+            let export_name = &path.path.segments.last().unwrap().ident;
+            let error = match export_name.to_string().strip_prefix(&prefix) {
+                Some(fn_name) if !fn_name.is_empty() => {
+                    let fn_name = Ident::new(fn_name, export_name.span());
+                    if fn_names.contains(&fn_name) {
+                        Error::new(
+                            export_name.span(),
+                            format!("`{}` is listed more than once", export_name),
+                        )
+                    } else {
+                        fn_names.push(fn_name);
+                        continue;
+                    }
+                }
+                _ => Error::new(
+                    export_name.span(),
+                    format!(
+                        "expected a method exported from `{}`, named like `{}<method>`",
+                        mod_name, prefix
+                    ),
+                ),
+            };
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(fn_names),
+        }
+    }
+
+    fn parse_item(self, attrs: TokenStream, item: TokenStream) -> Result<TokenStream> {
         let server: ItemStruct = parse2(item)?;
 
+        // Without `impls`, the methods are taken from the impl block marked with `#[servify::service]`.
+        let Some(impls) = self.impls.clone() else {
+            let macro_name = registry_macro_name(&server.ident);
+            return Ok(quote! {
+                self::#macro_name! { (#attrs) #server }
+            });
+        };
+
         let mod_name = server.ident.clone();
-        let server_items = server.fields;
 
-        let tokens: Vec<ImplTokens> = self
-            .impls
-            .clone()
+        if let Some(param) = server
+            .generics
+            .params
+            .iter()
+            .find(|param| !matches!(param, GenericParam::Type(_)))
+        {
+            return Err(Error::new(
+                param.span(),
+                "servify_macro::service only supports type parameters.",
+            ));
+        }
+        let generics = server.generics.clone();
+        let mod_vis = server.vis.clone();
+        // `#[cfg]` decides whether the whole module exists, docs describe both the module and
+        // `Server`, and everything else (derives, lints, ...) belongs to `Server` alone.
+        let mod_attrs = server
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg") || attr.path().is_ident("doc"))
+            .cloned()
+            .collect::<Vec<_>>();
+        let server_attrs = server
+            .attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("cfg"))
+            .cloned()
+            .collect::<Vec<_>>();
+        // The struct is re-emitted as-is under the name `Server`, so named, tuple and unit structs
+        // all keep their shape.
+        let server_struct = ItemStruct {
+            attrs: server_attrs,
+            vis: parse_quote! { pub },
+            ident: Ident::new("Server", Span::call_site()),
+            ..server
+        };
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        // `spawn`, and `listen` with `concurrent_reads`, move the server into tasks, so they need
+        // owned, thread-safe parameters.
+        let task_generics = generics.with_task_bounds();
+        let spawn_where_clause = task_generics.split_for_impl().2;
+        let listen_where_clause = match self.concurrent_reads {
+            true => spawn_where_clause,
+            false => where_clause,
+        };
+        let mut request_generics = generics.clone();
+        request_generics.params.push(parse_quote! { __Reply });
+        let request_impl_generics = request_generics.split_for_impl().0;
+
+        let fn_names = Self::fn_names(&impls, &mod_name)?;
+
+        let tokens: Vec<ImplTokens> = impls
             .into_iter()
-            .filter_map(|path| {
-                let fn_name = path.path.segments.last().unwrap().ident.clone();
-                let fn_name = Ident::new(
-                    &fn_name.to_string()
-                    .strip_prefix(&mod_name.to_string())
-                    .map(|p| p.trim_start_matches('_').to_string())?,
-                    fn_name.span(),
-                );
+            .zip(fn_names)
+            .map(|(path, fn_name)| {
                 let internal_fn_name = Ident::new(
                     &format!("__internal_{}", fn_name),
                     fn_name.span(),
                 );
+                let internal_try_fn_name = Ident::new(
+                    &format!("__internal_try_{}", fn_name),
+                    fn_name.span(),
+                );
+                let internal_send_fn_name = Ident::new(
+                    &format!("__internal_send_{}", fn_name),
+                    fn_name.span(),
+                );
+
+                let dispatch_fn_name = Ident::new(
+                    &format!("__dispatch_{}", fn_name),
+                    fn_name.span(),
+                );
 
                 let enum_name = Ident::new(&fn_name.to_string().to_camel(), fn_name.span());
 
                 let super_path = path.clone().to_super();
+                let super_path = quote! { #super_path #ty_generics };
+
+                // Spans are named after the call as the client sees it, e.g. `Counter::increment`.
+                let span_name = LitStr::new(&format!("{}::{}", mod_name, fn_name), fn_name.span());
 
                 let internal_function = quote! {
                     #[doc(hidden)]
-                    pub async fn #internal_fn_name(
-                        client: &Client,
+                    pub async fn #internal_try_fn_name #impl_generics(
+                        client: &Client #ty_generics,
                         req: <#super_path as ::servify::ServifyExport>::Request,
-                    ) -> <#super_path as ::servify::ServifyExport>::Response {
-                        let (tx, rx) = ::tokio::sync::oneshot::channel();
-                        client.tx.send(Message::#enum_name(req, tx)).await.unwrap();
-                        rx.await.unwrap()
+                    ) -> ::std::result::Result<<#super_path as ::servify::ServifyExport>::Response, ::servify::Error> #where_clause {
+                        let (tx, rx) = <<#super_path as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::channel();
+                        __request(client, Message::#enum_name(req, tx, ::servify::__call_span!(#span_name)), rx).await
+                    }
+
+                    #[doc(hidden)]
+                    pub async fn #internal_fn_name #impl_generics(
+                        client: &Client #ty_generics,
+                        req: <#super_path as ::servify::ServifyExport>::Request,
+                    ) -> <#super_path as ::servify::ServifyExport>::Response #where_clause {
+                        #internal_try_fn_name(client, req).await.unwrap()
+                    }
+
+                    #[doc(hidden)]
+                    pub async fn #internal_send_fn_name #impl_generics(
+                        client: &Client #ty_generics,
+                        req: <#super_path as ::servify::ServifyExport>::Request,
+                    ) -> ::std::result::Result<<<#super_path as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::Receiver, ::servify::Error> #where_clause {
+                        let (tx, rx) = <<#super_path as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::channel();
+                        __send(client, Message::#enum_name(req, tx, ::servify::__call_span!(#span_name))).await?;
+                        ::std::result::Result::Ok(rx)
                     }
                 };
 
                 let enum_element = quote! {
                     #enum_name(
                         <#super_path as ::servify::ServifyExport>::Request,
-                        ::tokio::sync::oneshot::Sender<<#super_path as ::servify::ServifyExport>::Response>,
+                        <#super_path as ::servify::ServifyExport>::Responder,
+                        ::servify::__private::CallSpan,
                     ),
                 };
 
-                let server_arm = quote! {
-                    Message::#enum_name(req, tx) => {
-                        let res = self.#fn_name(req).await;
-                        tx.send(res).unwrap();
-                    },
+                let skip_if_abandoned = self.skip_abandoned.then(|| quote! {
+                    if ::servify::__private::Responder::is_closed(&tx) {
+                        rx.abandoned.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                        continue;
+                    }
+                });
+
+                let (dispatch, after_reply) = match &self.catch_panics {
+                    None => (
+                        quote! {
+                            let res = ::std::result::Result::Ok(span.run(self.#dispatch_fn_name(req, &ctx)).await);
+                        },
+                        quote! {},
+                    ),
+                    Some(policy) => {
+                        let on_panic = match policy {
+                            PanicPolicy::Continue => None,
+                            PanicPolicy::Stop => Some(quote! { break; }),
+                            PanicPolicy::Restart(factory) => {
+                                let factory = TypePath {
+                                    qself: None,
+                                    path: factory.clone(),
+                                }
+                                .to_super();
+                                Some(quote! { self = #factory(); })
+                            }
+                        };
+                        let catch = quote! {
+                            let res = ::servify::__private::catch_unwind(span.run(self.#dispatch_fn_name(req, &ctx))).await;
+                        };
+                        match on_panic {
+                            None => (catch, quote! {}),
+                            Some(on_panic) => (
+                                quote! {
+                                    #catch
+                                    let panicked = res.is_err();
+                                },
+                                quote! {
+                                    if panicked {
+                                        #on_panic
+                                    }
+                                },
+                            ),
+                        }
+                    }
                 };
 
+                // The middleware sees the request before it is moved into the handler.
+                let method_name = fn_name.to_string();
+                let before = quote! {
+                    if let ::std::option::Option::Some(middleware) = &middleware {
+                        let request = {
+                            use ::servify::__private::{DebugProbe as _, FallbackProbe as _};
+                            (&::servify::__private::Probe(&req)).debug()
+                        };
+                        if let ::std::result::Result::Err(err) = ::servify::Middleware::before(&**middleware, &::servify::__private::call(#method_name, request)) {
+                            let _ = ::servify::__private::Responder::send(tx, ::std::result::Result::Err(err));
+                            continue;
+                        }
+                    }
+                    let started = ::std::time::Instant::now();
+                };
+                let after = quote! {
+                    if let ::std::option::Option::Some(middleware) = &middleware {
+                        let response = res.as_ref().map(|res| {
+                            use ::servify::__private::{DebugProbe as _, FallbackProbe as _};
+                            (&::servify::__private::Probe(res)).debug()
+                        });
+                        ::servify::Middleware::after(&**middleware, &::servify::__private::completed(#method_name, started.elapsed(), response));
+                    }
+                };
 
-                Some(ImplTokens {
+                let server_arm = if self.concurrent_reads {
+                    let locked_fn_name = Ident::new(
+                        &format!("__locked_{}", fn_name),
+                        fn_name.span(),
+                    );
+                    let res = match self.catch_panics {
+                        None => quote! { ::std::result::Result::Ok(span.run(call).await) },
+                        Some(_) => quote! { ::servify::__private::catch_unwind(span.run(call)).await },
+                    };
+                    quote! {
+                        Message::#enum_name(req, tx, span) => {
+                            #skip_if_abandoned
+                            #before
+                            let call = Server::#locked_fn_name(::std::sync::Arc::clone(&state), req, ctx.clone()).await;
+                            let abandoned = ::std::sync::Arc::clone(&rx.abandoned);
+                            let middleware = ::std::clone::Clone::clone(&middleware);
+                            tasks.spawn(async move {
+                                let res = #res;
+                                #after
+                                if ::servify::__private::Responder::send(tx, res).is_err() {
+                                    abandoned.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                                }
+                            });
+                        },
+                    }
+                } else {
+                    quote! {
+                        Message::#enum_name(req, tx, span) => {
+                            #skip_if_abandoned
+                            #before
+                            #dispatch
+                            #after
+                            if ::servify::__private::Responder::send(tx, res).is_err() {
+                                rx.abandoned.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                            }
+                            #after_reply
+                        },
+                    }
+                };
+
+
+                let reject_arm = quote! {
+                    Message::#enum_name(_, tx, _) => {
+                        let _ = ::servify::__private::Responder::send(tx, ::std::result::Result::Err(::servify::Error::ShuttingDown));
+                    },
+                };
+
+                ImplTokens {
                     internal_function,
                     enum_element,
                     server_arm,
-                })
+                    reject_arm,
+                }
             })
             .collect();
 
@@ -130,39 +607,395 @@ impl ServiceParentAttrs {
             tokens.iter().map(|t| t.internal_function.clone()).collect();
         let enum_elements: TokenStream = tokens.iter().map(|t| t.enum_element.clone()).collect();
         let server_arms: TokenStream = tokens.iter().map(|t| t.server_arm.clone()).collect();
+        let reject_arms: TokenStream = tokens.iter().map(|t| t.reject_arm.clone()).collect();
+
+        // The events type is written next to the struct, so an alias declared there resolves it
+        // the way the user wrote it, prelude types and generic arguments included.
+        let events_alias = format_ident!("__{}_events", mod_name);
+        let events_alias_item = self.events.as_ref().map(|events| {
+            let cfgs = mod_attrs.iter().filter(|attr| attr.path().is_ident("cfg"));
+            quote! {
+                #(#cfgs)*
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                #mod_vis type #events_alias = #events;
+            }
+        });
+        // Handlers emit events through a `Context`, which services without events still hand out.
+        let events = self
+            .events
+            .as_ref()
+            .map(|_| quote! { super::#events_alias })
+            .map(|events| EventTokens {
+                receiver_field: quote! { events: ::servify::Context<#events>, },
+                client_field: quote! { events: ::tokio::sync::broadcast::WeakSender<#events>, },
+                client_clone: quote! { events: self.events.clone(), },
+                subscribe: quote! {
+                    /// Returns a stream of the events the server emits from now on.
+                    pub fn subscribe(&self) -> ::servify::EventStream<#events> {
+                        ::servify::__private::subscribe(&self.events)
+                    }
+                },
+                init: quote! { let (context, events) = ::servify::__private::event_channel(); },
+                receiver_init: quote! { events: context, },
+                client_init: quote! { events, },
+            });
+        let context = match &events {
+            Some(_) => quote! { rx.events.clone() },
+            None => quote! { ::servify::__private::no_events() },
+        };
+        let EventTokens {
+            receiver_field,
+            client_field,
+            client_clone,
+            subscribe,
+            init,
+            receiver_init,
+            client_init,
+        } = events.unwrap_or_default();
+
+        // Lifecycle hooks run on the server itself, or on the locked state with `concurrent_reads`.
+        let hook_call = |hook: &Ident| match self.concurrent_reads {
+            true => quote! { state.write().await.#hook().await; },
+            false => quote! { self.#hook().await; },
+        };
+        let on_start = self.on_start.as_ref().map(hook_call);
+        let (idle_init, idle_branch, idle_reset) = match &self.on_idle {
+            Some((hook, after)) => {
+                let call = hook_call(hook);
+                // One timer for the whole loop, so only messages put off the idle hook, not
+                // ticks or a shutdown request.
+                (
+                    quote! {
+                        let mut idle = false;
+                        let idle_timer = ::tokio::time::sleep(#after);
+                        ::tokio::pin!(idle_timer);
+                    },
+                    quote! {
+                        () = &mut idle_timer, if !idle => {
+                            idle = true;
+                            #call
+                            continue;
+                        }
+                    },
+                    quote! {
+                        idle = false;
+                        idle_timer.as_mut().reset(::tokio::time::Instant::now() + #after);
+                    },
+                )
+            }
+            None => Default::default(),
+        };
+
+        // Ticks come before messages so a busy server still runs them on time.
+        let (tick_inits, tick_branches): (Vec<_>, Vec<_>) = self
+            .ticks
+            .iter()
+            .map(|(hook, every)| {
+                let interval = format_ident!("{}_interval", hook);
+                let call = hook_call(hook);
+                (
+                    quote! {
+                        let mut #interval = ::tokio::time::interval_at(::tokio::time::Instant::now() + #every, #every);
+                        #interval.set_missed_tick_behavior(::tokio::time::MissedTickBehavior::Delay);
+                    },
+                    quote! {
+                        _ = #interval.tick() => {
+                            #call
+                            continue;
+                        }
+                    },
+                )
+            })
+            .unzip();
+
+        // A middleware given at runtime takes the place of the one declared on the struct.
+        let middleware_init = match &self.middleware {
+            Some(middleware) => {
+                let middleware = middleware.clone().to_super();
+                quote! {
+                    let middleware = ::std::option::Option::Some(rx.middleware.take().unwrap_or_else(|| ::std::sync::Arc::new(<#middleware as ::std::default::Default>::default())));
+                }
+            }
+            None => quote! { let middleware = rx.middleware.take(); },
+        };
+
+        let receive = quote! {
+            let msg = ::tokio::select! {
+                biased;
+                mode = rx.shutdown.requested(), if shutdown.is_none() => {
+                    rx.messages.close();
+                    shutdown = ::std::option::Option::Some(mode);
+                    continue;
+                }
+                #(#tick_branches)*
+                msg = rx.messages.recv() => match msg {
+                    ::std::option::Option::Some(msg) => msg,
+                    ::std::option::Option::None => break,
+                },
+                #idle_branch
+            };
+            #idle_reset
+            if shutdown == ::std::option::Option::Some(::servify::ShutdownMode::Reject) {
+                match msg {
+                    #reject_arms
+                }
+                continue;
+            }
+        };
+
+        let listen = if self.concurrent_reads {
+            let unwrap_state = quote! {
+                match ::std::sync::Arc::try_unwrap(state) {
+                    ::std::result::Result::Ok(state) => state.into_inner(),
+                    ::std::result::Result::Err(_) => unreachable!("every handler has finished"),
+                }
+            };
+            // Without `catch_panics`, a panicking handler stops the server like it does in the
+            // exclusive loop, instead of leaving the state half-updated for the next request.
+            let joined = match self.catch_panics {
+                None => quote! {
+                    if let ::std::result::Result::Err(err) = joined {
+                        if err.is_panic() {
+                            ::std::panic::resume_unwind(err.into_panic());
+                        }
+                    }
+                },
+                Some(_) => quote! { let _ = joined; },
+            };
+            let finish = match &self.on_stop {
+                Some(hook) => quote! {
+                    let mut server = #unwrap_state;
+                    server.#hook().await;
+                    server
+                },
+                None => unwrap_state,
+            };
+            quote! {
+                impl #impl_generics Server #ty_generics #listen_where_clause {
+                    /// Handles messages until every client is dropped or a shutdown is requested,
+                    /// then returns the final server state. `&self` handlers run concurrently with
+                    /// each other, while `&mut self` handlers run exclusively.
+                    pub async fn listen(self, mut rx: Receiver #ty_generics) -> Self {
+                        let state = ::std::sync::Arc::new(::tokio::sync::RwLock::new(self));
+                        #on_start
+                        let mut tasks = ::tokio::task::JoinSet::new();
+                        let mut shutdown = ::std::option::Option::None;
+                        let ctx = #context;
+                        #middleware_init
+                        #idle_init
+                        #(#tick_inits)*
+                        loop {
+                            #receive
+                            while let ::std::option::Option::Some(joined) = tasks.try_join_next() {
+                                #joined
+                            }
+                            match msg {
+                                #server_arms
+                            }
+                        }
+                        while let ::std::option::Option::Some(joined) = tasks.join_next().await {
+                            #joined
+                        }
+                        #finish
+                    }
+                }
+            }
+        } else {
+            let on_stop = self.on_stop.as_ref().map(hook_call);
+            quote! {
+                impl #impl_generics Server #ty_generics #listen_where_clause {
+                    /// Handles messages until every client is dropped or a shutdown is requested,
+                    /// then returns the final server state.
+                    pub async fn listen(mut self, mut rx: Receiver #ty_generics) -> Self {
+                        #on_start
+                        let mut shutdown = ::std::option::Option::None;
+                        let ctx = #context;
+                        #middleware_init
+                        #idle_init
+                        #(#tick_inits)*
+                        loop {
+                            #receive
+                            match msg {
+                                #server_arms
+                            }
+                        }
+                        #on_stop
+                        self
+                    }
+                }
+            }
+        };
 
         Ok(quote! {
+            #events_alias_item
+
+            #(#mod_attrs)*
             #[allow(non_snake_case)]
-            mod #mod_name {
+            #mod_vis mod #mod_name {
 
-                pub enum Message {
+                pub enum Message #impl_generics #where_clause {
                     #enum_elements
                 }
 
-                pub struct Server #server_items
+                #server_struct
 
-                #[derive(Clone)]
-                pub struct Client {
-                    tx: ::tokio::sync::mpsc::Sender<Message>,
+                /// The receiving half of the message channel, consumed by `Server::listen`.
+                pub struct Receiver #impl_generics #where_clause {
+                    messages: ::tokio::sync::mpsc::Receiver<Message #ty_generics>,
+                    abandoned: ::std::sync::Arc<::std::sync::atomic::AtomicUsize>,
+                    shutdown: ::servify::__private::ShutdownSignal,
+                    middleware: ::std::option::Option<::std::sync::Arc<dyn ::servify::Middleware>>,
+                    #receiver_field
                 }
 
-                impl Server {
-                    pub async fn listen(&mut self, mut rx: ::tokio::sync::mpsc::Receiver<Message>) {
-                        while let Some(msg) = rx.recv().await {
-                            match msg {
-                                #server_arms
-                            }
+                impl #impl_generics Receiver #ty_generics #where_clause {
+                    /// Returns a handle that asks the server listening on this receiver to stop.
+                    pub fn shutdown_handle(&self) -> ::servify::ShutdownHandle {
+                        self.shutdown.handle()
+                    }
+
+                    /// Runs `middleware` around every request the server listening on this
+                    /// receiver handles.
+                    pub fn with_middleware(mut self, middleware: impl ::servify::Middleware) -> Self {
+                        self.middleware = ::std::option::Option::Some(::std::sync::Arc::new(middleware));
+                        self
+                    }
+                }
+
+                pub struct Client #impl_generics #where_clause {
+                    tx: ::tokio::sync::mpsc::Sender<Message #ty_generics>,
+                    timeout: ::std::option::Option<::std::time::Duration>,
+                    abandoned: ::std::sync::Arc<::std::sync::atomic::AtomicUsize>,
+                    #client_field
+                }
+
+                impl #impl_generics ::std::clone::Clone for Client #ty_generics #where_clause {
+                    fn clone(&self) -> Self {
+                        Self {
+                            tx: self.tx.clone(),
+                            timeout: self.timeout,
+                            abandoned: self.abandoned.clone(),
+                            #client_clone
                         }
                     }
                 }
 
+                impl #impl_generics Client #ty_generics #where_clause {
+                    /// Returns how many requests were abandoned by their caller before the server could reply.
+                    pub fn abandoned_requests(&self) -> usize {
+                        self.abandoned.load(::std::sync::atomic::Ordering::Relaxed)
+                    }
+
+                    /// Returns this client with a timeout applied to every call made through it.
+                    pub fn with_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                        self.timeout = ::std::option::Option::Some(timeout);
+                        self
+                    }
+
+                    /// Returns a copy of this client whose calls time out after `timeout`.
+                    pub fn timeout(&self, timeout: ::std::time::Duration) -> Self {
+                        self.clone().with_timeout(timeout)
+                    }
+
+                    #subscribe
+
+                    /// Returns a scheduler whose calls are delivered to the server after `delay`.
+                    pub fn after(&self, delay: ::std::time::Duration) -> Scheduler #ty_generics {
+                        self.at(::tokio::time::Instant::now() + delay)
+                    }
+
+                    /// Returns a scheduler whose calls are delivered to the server at `deadline`.
+                    pub fn at(&self, deadline: impl ::std::convert::Into<::tokio::time::Instant>) -> Scheduler #ty_generics {
+                        Scheduler {
+                            client: self.clone(),
+                            deadline: deadline.into(),
+                        }
+                    }
+                }
+
+                /// Makes calls that are delivered to the server later, created by `Client::after` and
+                /// `Client::at`. Every call returns a [`servify::Scheduled`] handle to cancel or await it.
+                pub struct Scheduler #impl_generics #where_clause {
+                    client: Client #ty_generics,
+                    deadline: ::tokio::time::Instant,
+                }
+
+                impl #impl_generics Scheduler #ty_generics #spawn_where_clause {
+                    #[doc(hidden)]
+                    pub fn __schedule<__Reply, __Call>(&self, call: impl ::std::ops::FnOnce(Client #ty_generics) -> __Call) -> ::servify::Scheduled<__Reply>
+                    where
+                        __Reply: ::std::marker::Send + 'static,
+                        __Call: ::std::future::Future<Output = ::std::result::Result<__Reply, ::servify::Error>> + ::std::marker::Send + 'static,
+                    {
+                        ::servify::__private::schedule(self.deadline, call(self.client.clone()))
+                    }
+                }
+
+                #listen
+
+                async fn __request #request_impl_generics(
+                    client: &Client #ty_generics,
+                    msg: Message #ty_generics,
+                    rx: impl ::std::future::Future<Output = ::std::result::Result<::std::result::Result<__Reply, ::servify::Error>, ::tokio::sync::oneshot::error::RecvError>>,
+                ) -> ::std::result::Result<__Reply, ::servify::Error> #where_clause {
+                    let call = async {
+                        client
+                            .tx
+                            .send(msg)
+                            .await
+                            .map_err(|_| ::servify::Error::Disconnected)?;
+                        rx.await.map_err(|_| ::servify::Error::ServerDropped)?
+                    };
+                    match client.timeout {
+                        ::std::option::Option::Some(timeout) => ::tokio::time::timeout(timeout, call)
+                            .await
+                            .map_err(|_| ::servify::Error::Timeout)?,
+                        ::std::option::Option::None => call.await,
+                    }
+                }
+
+                async fn __send #impl_generics(client: &Client #ty_generics, msg: Message #ty_generics) -> ::std::result::Result<(), ::servify::Error> #where_clause {
+                    let sent = match client.timeout {
+                        ::std::option::Option::Some(timeout) => ::tokio::time::timeout(timeout, client.tx.send(msg))
+                            .await
+                            .map_err(|_| ::servify::Error::Timeout)?,
+                        ::std::option::Option::None => client.tx.send(msg).await,
+                    };
+                    sent.map_err(|_| ::servify::Error::Disconnected)
+                }
+
                 #internal_functions
 
-                pub fn initiate_message_passing(buffer: usize) -> (::tokio::sync::mpsc::Receiver<Message>, Client) {
-                    let (tx, rx) = ::tokio::sync::mpsc::channel(buffer);
-                    let client = Client { tx };
+                pub fn initiate_message_passing #impl_generics(buffer: usize) -> (Receiver #ty_generics, Client #ty_generics) #where_clause {
+                    let (tx, messages) = ::tokio::sync::mpsc::channel(buffer);
+                    let abandoned = ::std::sync::Arc::new(::std::sync::atomic::AtomicUsize::new(0));
+                    #init
+                    let rx = Receiver {
+                        messages,
+                        abandoned: abandoned.clone(),
+                        shutdown: ::std::default::Default::default(),
+                        middleware: ::std::option::Option::None,
+                        #receiver_init
+                    };
+                    let client = Client {
+                        tx,
+                        timeout: ::std::option::Option::None,
+                        abandoned,
+                        #client_init
+                    };
                     (rx, client)
                 }
+
+                /// Spawns `server` as configured by `config`, returning a client for it and a
+                /// handle that resolves to the final server state once it stops.
+                pub fn spawn #impl_generics(server: Server #ty_generics, config: ::servify::SpawnConfig) -> (Client #ty_generics, ::tokio::task::JoinHandle<Server #ty_generics>) #spawn_where_clause {
+                    let (mut rx, client) = initiate_message_passing(config.buffer_size());
+                    rx.middleware = config.middleware_instance();
+                    rx.shutdown = config.shutdown_signal();
+                    let handle = config.spawn(server.listen(rx));
+                    (client, handle)
+                }
             }
         })
     }
@@ -190,7 +1023,8 @@ mod tests {
                     pub enum Message {
                         Increment(
                             <super::SomeStruct_increment as ::servify::ServifyExport>::Request,
-                            ::tokio::sync::oneshot::Sender<<super::SomeStruct_increment as ::servify::ServifyExport>::Response>,
+                            <super::SomeStruct_increment as ::servify::ServifyExport>::Responder,
+                            ::servify::__private::CallSpan,
                         ),
                     }
 
@@ -198,41 +1032,330 @@ mod tests {
                         pub count: u32,
                     }
 
-                    #[derive(Clone)]
+                    /// The receiving half of the message channel, consumed by `Server::listen`.
+                    pub struct Receiver {
+                        messages: ::tokio::sync::mpsc::Receiver<Message>,
+                        abandoned: ::std::sync::Arc<::std::sync::atomic::AtomicUsize>,
+                        shutdown: ::servify::__private::ShutdownSignal,
+                        middleware: ::std::option::Option<::std::sync::Arc<dyn ::servify::Middleware>>,
+                    }
+
+                    impl Receiver {
+                        /// Returns a handle that asks the server listening on this receiver to stop.
+                        pub fn shutdown_handle(&self) -> ::servify::ShutdownHandle {
+                            self.shutdown.handle()
+                        }
+
+                        /// Runs `middleware` around every request the server listening on this
+                        /// receiver handles.
+                        pub fn with_middleware(mut self, middleware: impl ::servify::Middleware) -> Self {
+                            self.middleware = ::std::option::Option::Some(::std::sync::Arc::new(middleware));
+                            self
+                        }
+                    }
+
                     pub struct Client {
                         tx: ::tokio::sync::mpsc::Sender<Message>,
+                        timeout: ::std::option::Option<::std::time::Duration>,
+                        abandoned: ::std::sync::Arc<::std::sync::atomic::AtomicUsize>,
+                    }
+
+                    impl ::std::clone::Clone for Client {
+                        fn clone(&self) -> Self {
+                            Self {
+                                tx: self.tx.clone(),
+                                timeout: self.timeout,
+                                abandoned: self.abandoned.clone(),
+                            }
+                        }
+                    }
+
+                    impl Client {
+                        /// Returns how many requests were abandoned by their caller before the server could reply.
+                        pub fn abandoned_requests(&self) -> usize {
+                            self.abandoned.load(::std::sync::atomic::Ordering::Relaxed)
+                        }
+
+                        /// Returns this client with a timeout applied to every call made through it.
+                        pub fn with_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                            self.timeout = ::std::option::Option::Some(timeout);
+                            self
+                        }
+
+                        /// Returns a copy of this client whose calls time out after `timeout`.
+                        pub fn timeout(&self, timeout: ::std::time::Duration) -> Self {
+                            self.clone().with_timeout(timeout)
+                        }
+
+                        /// Returns a scheduler whose calls are delivered to the server after `delay`.
+                        pub fn after(&self, delay: ::std::time::Duration) -> Scheduler {
+                            self.at(::tokio::time::Instant::now() + delay)
+                        }
+
+                        /// Returns a scheduler whose calls are delivered to the server at `deadline`.
+                        pub fn at(&self, deadline: impl ::std::convert::Into<::tokio::time::Instant>) -> Scheduler {
+                            Scheduler {
+                                client: self.clone(),
+                                deadline: deadline.into(),
+                            }
+                        }
+                    }
+
+                    /// Makes calls that are delivered to the server later, created by `Client::after` and
+                    /// `Client::at`. Every call returns a [`servify::Scheduled`] handle to cancel or await it.
+                    pub struct Scheduler {
+                        client: Client,
+                        deadline: ::tokio::time::Instant,
+                    }
+
+                    impl Scheduler {
+                        #[doc(hidden)]
+                        pub fn __schedule<__Reply, __Call>(&self, call: impl ::std::ops::FnOnce(Client) -> __Call) -> ::servify::Scheduled<__Reply>
+                        where
+                            __Reply: ::std::marker::Send + 'static,
+                            __Call: ::std::future::Future<Output = ::std::result::Result<__Reply, ::servify::Error>> + ::std::marker::Send + 'static,
+                        {
+                            ::servify::__private::schedule(self.deadline, call(self.client.clone()))
+                        }
                     }
 
                     impl Server {
-                        pub async fn listen(&mut self, mut rx: ::tokio::sync::mpsc::Receiver<Message>) {
-                            while let Some(msg) = rx.recv().await {
+                        /// Handles messages until every client is dropped or a shutdown is requested,
+                        /// then returns the final server state.
+                        pub async fn listen(mut self, mut rx: Receiver) -> Self {
+                            let mut shutdown = ::std::option::Option::None;
+                            let ctx = ::servify::__private::no_events();
+                            let middleware = rx.middleware.take();
+                            loop {
+                                let msg = ::tokio::select! { biased ; mode = rx . shutdown . requested () , if shutdown . is_none () => { rx . messages . close () ; shutdown = :: std :: option :: Option :: Some (mode) ; continue ; } msg = rx . messages . recv () => match msg { :: std :: option :: Option :: Some (msg) => msg , :: std :: option :: Option :: None => break , } , };
+                                if shutdown == ::std::option::Option::Some(::servify::ShutdownMode::Reject) {
+                                    match msg {
+                                        Message::Increment(_, tx, _) => {
+                                            let _ = ::servify::__private::Responder::send(tx, ::std::result::Result::Err(::servify::Error::ShuttingDown));
+                                        },
+                                    }
+
+                                    continue;
+                                }
+
                                 match msg {
-                                    Message::Increment(req, tx) => {
-                                        let res = self.increment(req).await;
-                                        tx.send(res).unwrap();
+                                    Message::Increment(req, tx, span) => {
+                                        if let ::std::option::Option::Some(middleware) = &middleware {
+                                            let request = {
+                                                use ::servify::__private::{DebugProbe as _, FallbackProbe as _};
+                                                (&::servify::__private::Probe(&req)).debug()
+                                            };
+                                            if let ::std::result::Result::Err(err) = ::servify::Middleware::before(&**middleware, &::servify::__private::call("increment", request)) {
+                                                let _ = ::servify::__private::Responder::send(tx, ::std::result::Result::Err(err));
+                                                continue;
+                                            }
+                                        }
+
+                                        let started = ::std::time::Instant::now();
+                                        let res = ::std::result::Result::Ok(span.run(self.__dispatch_increment(req, &ctx)).await);
+                                        if let ::std::option::Option::Some(middleware) = &middleware {
+                                            let response = res.as_ref().map(|res| {
+                                                use ::servify::__private::{DebugProbe as _, FallbackProbe as _};
+                                                (&::servify::__private::Probe(res)).debug()
+                                            });
+                                            ::servify::Middleware::after(&**middleware, &::servify::__private::completed("increment", started.elapsed(), response));
+                                        }
+
+                                        if ::servify::__private::Responder::send(tx, res).is_err() {
+                                            rx.abandoned.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                                        }
                                     },
                                 }
                             }
+
+                            self
+                        }
+                    }
+
+                    async fn __request<__Reply>(
+                        client: &Client,
+                        msg: Message,
+                        rx: impl ::std::future::Future<Output = ::std::result::Result<::std::result::Result<__Reply, ::servify::Error>, ::tokio::sync::oneshot::error::RecvError>>,
+                    ) -> ::std::result::Result<__Reply, ::servify::Error> {
+                        let call = async {
+                            client.tx.send(msg).await.map_err(|_| ::servify::Error::Disconnected)?;
+                            rx.await.map_err(|_| ::servify::Error::ServerDropped)?
+                        };
+                        match client.timeout {
+                            ::std::option::Option::Some(timeout) => ::tokio::time::timeout(timeout, call).await.map_err(|_| ::servify::Error::Timeout)?,
+                            ::std::option::Option::None => call.await,
                         }
                     }
 
+                    async fn __send(client: &Client, msg: Message) -> ::std::result::Result<(), ::servify::Error> {
+                        let sent = match client.timeout {
+                            ::std::option::Option::Some(timeout) => ::tokio::time::timeout(timeout, client.tx.send(msg)).await.map_err(|_| ::servify::Error::Timeout)?,
+                            ::std::option::Option::None => client.tx.send(msg).await,
+                        };
+                        sent.map_err(|_| ::servify::Error::Disconnected)
+                    }
+
+                    #[doc(hidden)]
+                    pub async fn __internal_try_increment(
+                        client: &Client,
+                        req: <super::SomeStruct_increment as ::servify::ServifyExport>::Request,
+                    ) -> ::std::result::Result<<super::SomeStruct_increment as ::servify::ServifyExport>::Response, ::servify::Error> {
+                        let (tx, rx) = <<super::SomeStruct_increment as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::channel();
+                        __request(client, Message::Increment(req, tx, ::servify::__call_span!("SomeStruct::increment")), rx).await
+                    }
+
                     #[doc(hidden)]
                     pub async fn __internal_increment(
                         client: &Client,
                         req: <super::SomeStruct_increment as ::servify::ServifyExport>::Request,
-                    ) ->  <super::SomeStruct_increment as ::servify::ServifyExport>::Response {
-                        let (tx, rx) = ::tokio::sync::oneshot::channel();
-                        client.tx.send(Message::Increment(req, tx)).await.unwrap();
-                        rx.await.unwrap()
+                    ) -> <super::SomeStruct_increment as ::servify::ServifyExport>::Response {
+                        __internal_try_increment(client, req).await.unwrap()
+                    }
+
+                    #[doc(hidden)]
+                    pub async fn __internal_send_increment(
+                        client: &Client,
+                        req: <super::SomeStruct_increment as ::servify::ServifyExport>::Request,
+                    ) -> ::std::result::Result<<<super::SomeStruct_increment as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::Receiver, ::servify::Error> {
+                        let (tx, rx) = <<super::SomeStruct_increment as ::servify::ServifyExport>::Responder as ::servify::__private::Responder>::channel();
+                        __send(client, Message::Increment(req, tx, ::servify::__call_span!("SomeStruct::increment"))).await?;
+                        ::std::result::Result::Ok(rx)
                     }
 
-                    pub fn initiate_message_passing(buffer: usize) -> (::tokio::sync::mpsc::Receiver<Message>, Client) {
-                        let (tx, rx) = ::tokio::sync::mpsc::channel(buffer);
-                        let client = Client { tx };
+                    pub fn initiate_message_passing(buffer: usize) -> (Receiver, Client) {
+                        let (tx, messages) = ::tokio::sync::mpsc::channel(buffer);
+                        let abandoned = ::std::sync::Arc::new(::std::sync::atomic::AtomicUsize::new(0));
+                        let rx = Receiver {
+                            messages,
+                            abandoned: abandoned.clone(),
+                            shutdown: ::std::default::Default::default(),
+                            middleware: ::std::option::Option::None,
+                        };
+                        let client = Client {
+                            tx,
+                            timeout: ::std::option::Option::None,
+                            abandoned,
+                        };
                         (rx, client)
                     }
+
+                    /// Spawns `server` as configured by `config`, returning a client for it and a
+                    /// handle that resolves to the final server state once it stops.
+                    pub fn spawn(server: Server, config: ::servify::SpawnConfig) -> (Client, ::tokio::task::JoinHandle<Server>) {
+                        let (mut rx, client) = initiate_message_passing(config.buffer_size());
+                        rx.middleware = config.middleware_instance();
+                        rx.shutdown = config.shutdown_signal();
+                        let handle = config.spawn(server.listen(rx));
+                        (client, handle)
+                    }
                 }
             }.to_string(),
         };
     }
+
+    #[test]
+    fn fail_if_unknown_panic_policy() {
+        assert_eq! {
+            impl_service(quote!{
+                impls = [SomeStruct_increment],
+                catch_panics(ignore),
+            }, quote!{
+                struct SomeStruct {
+                    pub count: u32,
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "Unknown panic policy. expected `continue`, `stop` or `restart = <factory>`" }"#,
+        };
+    }
+
+    #[test]
+    fn fail_if_concurrent_reads_with_stop() {
+        assert_eq! {
+            impl_service(quote!{
+                impls = [SomeStruct_increment],
+                concurrent_reads,
+                catch_panics(stop),
+            }, quote!{
+                struct SomeStruct {
+                    pub count: u32,
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "`concurrent_reads` can only be combined with `catch_panics(continue)`" }"#,
+        };
+    }
+
+    #[test]
+    fn fail_if_hook_set_twice() {
+        assert_eq! {
+            impl_service(quote!{
+                impls = [SomeStruct_increment],
+                on_stop = save,
+                on_stop = flush,
+            }, quote!{
+                struct SomeStruct {
+                    pub count: u32,
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "`on_stop` is set more than once" }"#,
+        };
+    }
+
+    #[test]
+    fn fail_if_impls_prefix_mismatch() {
+        assert_eq! {
+            impl_service(quote!{
+                impls = [OtherStruct_increment],
+            }, quote!{
+                struct SomeStruct {
+                    pub count: u32,
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "expected a method exported from `SomeStruct`, named like `SomeStruct_<method>`" }"#,
+        };
+    }
+
+    #[test]
+    fn fail_if_impls_duplicated() {
+        assert_eq! {
+            impl_service(quote!{
+                impls = [SomeStruct_increment, SomeStruct_increment],
+            }, quote!{
+                struct SomeStruct {
+                    pub count: u32,
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "`SomeStruct_increment` is listed more than once" }"#,
+        };
+    }
+
+    #[test]
+    fn fail_if_derive_on_struct() {
+        assert_eq! {
+            impl_service(quote!{
+                impls = [SomeStruct_increment],
+                derive(Debug),
+            }, quote!{
+                struct SomeStruct {
+                    pub count: u32,
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "Request derives are chosen per impl block. Put `derive(...)` on `#[servify::export]` or on the impl block marked with `#[servify::service]`." }"#,
+        };
+    }
+
+    #[test]
+    fn fail_if_options_on_impl() {
+        assert_eq! {
+            impl_service(quote!{
+                catch_panics,
+            }, quote!{
+                impl SomeStruct {
+                    fn increment(&mut self) {
+                        self.count += 1;
+                    }
+                }
+            }).to_string(),
+            r#":: core :: compile_error ! { "Options of servify_macro::service other than `derive` belong on the struct, not on the impl block." }"#,
+        };
+    }
 }
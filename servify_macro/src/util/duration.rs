@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr, Result,
+};
+
+/// A duration written as a string literal like `"500ms"`, `"30s"`, `"5m"` or `"1h"`.
+pub(crate) struct DurationLit {
+    pub(crate) lit: LitStr,
+    millis: u64,
+}
+
+impl Parse for DurationLit {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lit: LitStr = input.parse()?;
+        let value = lit.value();
+        let (amount, unit) = value.split_at(
+            value
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(value.len()),
+        );
+        let factor = match unit {
+            "ms" => Some(1),
+            "s" => Some(1_000),
+            "m" => Some(60_000),
+            "h" => Some(3_600_000),
+            _ => None,
+        };
+        let millis = match (amount.parse::<u64>(), factor) {
+            (Ok(amount), Some(factor)) => amount.checked_mul(factor),
+            _ => None,
+        };
+        match millis {
+            Some(0) => Err(Error::new(
+                lit.span(),
+                "the duration must be longer than zero",
+            )),
+            Some(millis) => Ok(Self { lit, millis }),
+            None => Err(Error::new(
+                lit.span(),
+                "expected a duration like `500ms`, `30s`, `5m` or `1h`",
+            )),
+        }
+    }
+}
+
+impl ToTokens for DurationLit {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let millis = self.millis;
+        tokens.extend(quote! { ::std::time::Duration::from_millis(#millis) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use quote::{quote, ToTokens};
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn parse_units() {
+        for (lit, millis) in [
+            ("250ms", 250u64),
+            ("30s", 30_000),
+            ("5m", 300_000),
+            ("1h", 3_600_000),
+        ] {
+            assert_eq! {
+                parse2::<DurationLit>(quote! { #lit }).unwrap().to_token_stream().to_string(),
+                quote! { ::std::time::Duration::from_millis(#millis) }.to_string()
+            }
+        }
+    }
+
+    #[test]
+    fn fail_if_unknown_unit() {
+        for lit in ["30", "5 s", "1d", "s", "-1s"] {
+            assert_eq! {
+                parse2::<DurationLit>(quote! { #lit }).err().unwrap().to_string(),
+                "expected a duration like `500ms`, `30s`, `5m` or `1h`"
+            }
+        }
+    }
+
+    #[test]
+    fn fail_if_zero() {
+        assert_eq! {
+            parse2::<DurationLit>(quote! { "0s" }).err().unwrap().to_string(),
+            "the duration must be longer than zero"
+        }
+    }
+}
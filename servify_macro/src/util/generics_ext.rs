@@ -0,0 +1,48 @@
+use proc_macro2::Span;
+use syn::{parse_quote, Generics, Ident};
+
+pub(crate) trait GenericsExt {
+    /// Adds `Send + Sync + 'static` bounds to every type parameter, for items that move values
+    /// of these types into tasks.
+    fn with_task_bounds(&self) -> Generics;
+}
+
+impl GenericsExt for Generics {
+    fn with_task_bounds(&self) -> Generics {
+        let mut generics = self.clone();
+        // Call-site spans keep clippy from treating the bounds as written twice by the user.
+        let type_params = self
+            .type_params()
+            .map(|param| Ident::new(&param.ident.to_string(), Span::call_site()))
+            .collect::<Vec<_>>();
+        let where_clause = generics.make_where_clause();
+        for param in type_params {
+            where_clause.predicates.push(parse_quote! {
+                #param: ::std::marker::Send + ::std::marker::Sync + 'static
+            });
+        }
+        generics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use quote::{quote, ToTokens};
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn with_task_bounds() {
+        let generics: Generics = parse2(quote! { <K, V: Clone> }).unwrap();
+        assert_eq! {
+            generics.with_task_bounds().split_for_impl().2.to_token_stream().to_string(),
+            quote! {
+                where
+                    K: ::std::marker::Send + ::std::marker::Sync + 'static,
+                    V: ::std::marker::Send + ::std::marker::Sync + 'static
+            }.to_string()
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod duration;
+pub mod generics_ext;
 pub mod return_type_ext;
 pub mod type_path_ext;